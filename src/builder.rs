@@ -0,0 +1,240 @@
+//! Builder for configuring a Podbean API client.
+//!
+//! This module defines [`PodbeanClientBuilder`], which allows overriding
+//! the endpoints, HTTP settings and token persistence used by
+//! [`PodbeanClient`].
+
+use crate::rate_limit::{RateLimit, RateLimiter};
+use crate::retry::RetryPolicy;
+use crate::store::TokenStore;
+use crate::transport::{ReqwestTransport, Transport};
+use crate::types::SecretString;
+use crate::{PodbeanClient, PodbeanResult};
+use reqwest::{Client, Proxy};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default base URL of the Podbean API.
+const DEFAULT_BASE_URL: &str = "https://api.podbean.com/v1";
+
+/// Default timeout applied to every request.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default timeout for transferring a media file to its presigned URL.
+const DEFAULT_UPLOAD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Default `User-Agent` header sent with every request.
+const DEFAULT_USER_AGENT: &str = concat!("podbean/", env!("CARGO_PKG_VERSION"));
+
+/// A builder for [`PodbeanClient`].
+///
+/// The OAuth token and dialog URLs default to paths under the API base URL,
+/// so pointing [`base_url`](PodbeanClientBuilder::base_url) at a local
+/// stand-in server redirects OAuth requests as well.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use podbean::PodbeanClient;
+/// use std::time::Duration;
+///
+/// let client = PodbeanClient::builder("your_client_id", "your_client_secret")
+///     .base_url("http://127.0.0.1:8080/v1")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PodbeanClientBuilder {
+    client_id: String,
+    client_secret: SecretString,
+    base_url: String,
+    token_url: Option<String>,
+    dialog_url: Option<String>,
+    timeout: Duration,
+    upload_timeout: Duration,
+    user_agent: String,
+    proxies: Vec<Proxy>,
+    http_client: Option<Client>,
+    transport: Option<Arc<dyn Transport>>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    rate_limit: Option<RateLimit>,
+    retry_policy: RetryPolicy,
+}
+
+impl PodbeanClientBuilder {
+    /// Creates a builder with the default Podbean settings.
+    pub fn new(client_id: &str, client_secret: &str) -> Self {
+        Self {
+            client_id: client_id.to_string(),
+            client_secret: SecretString::from(client_secret),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            token_url: None,
+            dialog_url: None,
+            timeout: DEFAULT_TIMEOUT,
+            upload_timeout: DEFAULT_UPLOAD_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            proxies: Vec::new(),
+            http_client: None,
+            transport: None,
+            token_store: None,
+            rate_limit: Some(RateLimit::default()),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Sets the base URL of the API, e.g. `https://api.podbean.com/v1`.
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the OAuth token endpoint URL.
+    ///
+    /// Defaults to `{base_url}/oauth/token`. The other OAuth endpoints,
+    /// such as `debugToken` and `revokeToken`, are resolved next to it.
+    pub fn token_url(mut self, token_url: &str) -> Self {
+        self.token_url = Some(token_url.to_string());
+        self
+    }
+
+    /// Sets the OAuth authorization dialog URL.
+    ///
+    /// Defaults to `{base_url}/dialog/oauth`.
+    pub fn dialog_url(mut self, dialog_url: &str) -> Self {
+        self.dialog_url = Some(dialog_url.to_string());
+        self
+    }
+
+    /// Sets the timeout applied to every request. Defaults to 30 seconds.
+    ///
+    /// Ignored when an HTTP client is injected with
+    /// [`http_client`](PodbeanClientBuilder::http_client).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for transferring a media file. Defaults to one hour.
+    ///
+    /// Uploads are large, so they get their own timeout instead of the one
+    /// applied to API calls. It can be overridden per upload with
+    /// [`UploadOptions::timeout`](crate::UploadOptions::timeout).
+    pub fn upload_timeout(mut self, timeout: Duration) -> Self {
+        self.upload_timeout = timeout;
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    ///
+    /// Ignored when an HTTP client is injected with
+    /// [`http_client`](PodbeanClientBuilder::http_client).
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Adds a proxy to route requests through.
+    ///
+    /// Ignored when an HTTP client is injected with
+    /// [`http_client`](PodbeanClientBuilder::http_client).
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Uses the given `reqwest::Client` instead of building one.
+    pub fn http_client(mut self, client: Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sends requests through the given transport instead of `reqwest`.
+    ///
+    /// Use this to run the client against an in-memory fake in tests. The
+    /// HTTP settings of this builder are ignored when a transport is set.
+    pub fn transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Persists tokens with the given store.
+    ///
+    /// See [`PodbeanClient::with_token_store`].
+    pub fn token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Sets the client-side rate limit. Defaults to [`RateLimit::default`].
+    ///
+    /// The limiter is shared by every clone of the built client.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = Some(limit);
+        self
+    }
+
+    /// Disables client-side rate limiting.
+    pub fn disable_rate_limit(mut self) -> Self {
+        self.rate_limit = None;
+        self
+    }
+
+    /// Sets the policy for retrying failed requests.
+    ///
+    /// Defaults to [`RetryPolicy::default`], which retries rate limited
+    /// requests and idempotent requests that failed otherwise.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PodbeanClient)` if the client was built successfully
+    /// * `Err(PodbeanError)` if the HTTP client could not be created
+    pub fn build(self) -> PodbeanResult<PodbeanClient> {
+        let transport = match (self.transport, self.http_client) {
+            (Some(transport), _) => transport,
+            (None, Some(client)) => Arc::new(ReqwestTransport::new(client)),
+            (None, None) => {
+                let mut builder = Client::builder()
+                    .timeout(self.timeout)
+                    .user_agent(self.user_agent);
+
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+
+                Arc::new(ReqwestTransport::new(builder.build()?))
+            }
+        };
+
+        let token_url = self
+            .token_url
+            .unwrap_or_else(|| format!("{}/oauth/token", self.base_url));
+        let dialog_url = self
+            .dialog_url
+            .unwrap_or_else(|| format!("{}/dialog/oauth", self.base_url));
+
+        Ok(PodbeanClient {
+            transport,
+            client_id: self.client_id,
+            client_secret: self.client_secret,
+            base_url: self.base_url,
+            token_url,
+            dialog_url,
+            token: Arc::new(Mutex::new(None)),
+            podcast_tokens: Arc::new(Mutex::new(HashMap::new())),
+            token_store: self.token_store,
+            rate_limiter: self.rate_limit.map(RateLimiter::new),
+            retry_policy: self.retry_policy,
+            upload_timeout: self.upload_timeout,
+        })
+    }
+}
@@ -0,0 +1,679 @@
+//! Request types for creating and editing episodes.
+//!
+//! This module defines [`NewEpisode`], which describes an episode to
+//! publish, and [`EpisodeUpdate`], a partial update of an existing episode.
+//! Both are validated before they are sent to the Podbean API.
+
+use crate::id::{EpisodeId, MediaKey, PodcastId};
+use crate::timestamp::Timestamp;
+use crate::types::{AppleEpisodeType, ContentExplicit, EpisodeStatus, EpisodeType};
+use crate::{PodbeanError, PodbeanResult};
+use std::collections::HashMap;
+
+/// Maximum length of an episode title accepted by Podbean.
+const MAX_TITLE_LENGTH: usize = 200;
+
+/// A new episode to publish with
+/// [`PodbeanClient::publish_episode`](crate::PodbeanClient::publish_episode).
+///
+/// Episodes are created as public drafts unless configured otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::{EpisodeStatus, MediaKey, NewEpisode, PodcastId};
+///
+/// let podcast_id = PodcastId::from("podcast_id");
+/// let media_key = MediaKey::from("media_key");
+///
+/// let episode = NewEpisode::new(&podcast_id, "My New Episode", "Show notes...")
+///     .media_key(&media_key)
+///     .status(EpisodeStatus::Publish)
+///     .season_number(2)
+///     .episode_number(14);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NewEpisode {
+    podcast_id: PodcastId,
+    title: String,
+    content: String,
+    status: EpisodeStatus,
+    episode_type: EpisodeType,
+    media_key: Option<MediaKey>,
+    logo_key: Option<MediaKey>,
+    transcripts_key: Option<MediaKey>,
+    publish_time: Option<Timestamp>,
+    season_number: Option<u32>,
+    episode_number: Option<u32>,
+    apple_episode_type: Option<AppleEpisodeType>,
+    content_explicit: Option<ContentExplicit>,
+}
+
+impl NewEpisode {
+    /// Creates a public draft episode.
+    ///
+    /// # Arguments
+    ///
+    /// * `podcast_id` - The ID of the podcast to publish to
+    /// * `title` - The title of the episode
+    /// * `content` - The description or show notes for the episode
+    pub fn new(podcast_id: &PodcastId, title: &str, content: &str) -> Self {
+        Self {
+            podcast_id: podcast_id.clone(),
+            title: title.to_string(),
+            content: content.to_string(),
+            status: EpisodeStatus::Draft,
+            episode_type: EpisodeType::Public,
+            media_key: None,
+            logo_key: None,
+            transcripts_key: None,
+            publish_time: None,
+            season_number: None,
+            episode_number: None,
+            apple_episode_type: None,
+            content_explicit: None,
+        }
+    }
+
+    /// Sets the publication status. Defaults to [`EpisodeStatus::Draft`].
+    ///
+    /// [`EpisodeStatus::Future`] requires a
+    /// [`publish_time`](NewEpisode::publish_time).
+    pub fn status(mut self, status: EpisodeStatus) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the episode type. Defaults to [`EpisodeType::Public`].
+    pub fn episode_type(mut self, episode_type: EpisodeType) -> Self {
+        self.episode_type = episode_type;
+        self
+    }
+
+    /// Sets the media key returned from `upload_media`.
+    pub fn media_key(mut self, media_key: &MediaKey) -> Self {
+        self.media_key = Some(media_key.clone());
+        self
+    }
+
+    /// Sets the file key of an uploaded episode logo.
+    pub fn logo_key(mut self, logo_key: &MediaKey) -> Self {
+        self.logo_key = Some(logo_key.clone());
+        self
+    }
+
+    /// Sets the file key of an uploaded transcript.
+    pub fn transcripts_key(mut self, transcripts_key: &MediaKey) -> Self {
+        self.transcripts_key = Some(transcripts_key.clone());
+        self
+    }
+
+    /// Sets the publishing time.
+    ///
+    /// Accepts a [`Timestamp`], a `SystemTime`, or with the `time` feature an
+    /// `OffsetDateTime`. Episodes are listed by publishing time, from new to
+    /// old. If it is not set, the current time is used.
+    pub fn publish_time(mut self, time: impl Into<Timestamp>) -> Self {
+        self.publish_time = Some(time.into());
+        self
+    }
+
+    /// Sets the season number, starting at one.
+    pub fn season_number(mut self, season_number: u32) -> Self {
+        self.season_number = Some(season_number);
+        self
+    }
+
+    /// Sets the episode number, starting at one.
+    pub fn episode_number(mut self, episode_number: u32) -> Self {
+        self.episode_number = Some(episode_number);
+        self
+    }
+
+    /// Sets the episode type reported to Apple Podcasts.
+    pub fn apple_episode_type(mut self, apple_episode_type: AppleEpisodeType) -> Self {
+        self.apple_episode_type = Some(apple_episode_type);
+        self
+    }
+
+    /// Sets the explicit content rating.
+    pub fn content_explicit(mut self, content_explicit: ContentExplicit) -> Self {
+        self.content_explicit = Some(content_explicit);
+        self
+    }
+
+    /// Gets the ID of the podcast to publish to.
+    pub(crate) fn podcast_id(&self) -> &PodcastId {
+        &self.podcast_id
+    }
+
+    /// Checks that the episode can be published.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if all required fields are present and valid
+    /// * `Err(PodbeanError::ValidationError)` naming the first invalid field
+    pub fn validate(&self) -> PodbeanResult<()> {
+        if self.podcast_id.as_str().trim().is_empty() {
+            return Err(PodbeanError::validation("podcast_id", "must not be empty"));
+        }
+
+        validate_title(&self.title)?;
+
+        if self.content.trim().is_empty() {
+            return Err(PodbeanError::validation("content", "must not be empty"));
+        }
+
+        if self.status == EpisodeStatus::Future && self.publish_time.is_none() {
+            return Err(PodbeanError::validation(
+                "publish_time",
+                "is required for future episodes",
+            ));
+        }
+
+        if self.season_number == Some(0) {
+            return Err(PodbeanError::validation(
+                "season_number",
+                "must be at least 1",
+            ));
+        }
+
+        if self.episode_number == Some(0) {
+            return Err(PodbeanError::validation(
+                "episode_number",
+                "must be at least 1",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Converts the episode into request parameters.
+    pub(crate) fn to_params(&self) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+        let _ = params.insert("podcast_id".to_string(), self.podcast_id.to_string());
+        let _ = params.insert("title".to_string(), self.title.clone());
+        let _ = params.insert("content".to_string(), self.content.clone());
+        let _ = params.insert("status".to_string(), self.status.to_string());
+        let _ = params.insert("type".to_string(), self.episode_type.to_string());
+
+        let optional = [
+            ("media_key", self.media_key.as_ref().map(|k| k.to_string())),
+            ("logo_key", self.logo_key.as_ref().map(|k| k.to_string())),
+            (
+                "transcripts_key",
+                self.transcripts_key.as_ref().map(|k| k.to_string()),
+            ),
+            (
+                "publish_timestamp",
+                self.publish_time.map(|t| t.unix_timestamp().to_string()),
+            ),
+            ("season_number", self.season_number.map(|n| n.to_string())),
+            ("episode_number", self.episode_number.map(|n| n.to_string())),
+            (
+                "apple_episode_type",
+                self.apple_episode_type.map(|t| t.to_string()),
+            ),
+            (
+                "content_explicit",
+                self.content_explicit.map(|c| c.to_string()),
+            ),
+        ];
+
+        for (key, value) in optional {
+            if let Some(value) = value {
+                let _ = params.insert(key.to_string(), value);
+            }
+        }
+
+        params
+    }
+}
+
+/// A change to an optional field in a partial update.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FieldUpdate<T> {
+    /// Leave the field unchanged
+    #[default]
+    Keep,
+    /// Set the field to a new value
+    Set(T),
+    /// Remove the current value of the field
+    Clear,
+}
+
+impl<T: ToString> FieldUpdate<T> {
+    /// Adds the change to request parameters under `key`.
+    ///
+    /// A cleared field is sent as an empty value.
+    pub(crate) fn insert_param(&self, params: &mut HashMap<String, String>, key: &str) {
+        let value = match self {
+            FieldUpdate::Keep => return,
+            FieldUpdate::Set(value) => value.to_string(),
+            FieldUpdate::Clear => String::new(),
+        };
+
+        let _ = params.insert(key.to_string(), value);
+    }
+}
+
+/// A partial update of an existing episode for
+/// [`PodbeanClient::update_episode`](crate::PodbeanClient::update_episode).
+///
+/// Fields that are not set are left unchanged. Optional fields can also be
+/// cleared with the matching `clear_*` method.
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::{EpisodeStatus, EpisodeUpdate};
+///
+/// let update = EpisodeUpdate::new()
+///     .title("Updated Title")
+///     .status(EpisodeStatus::Publish)
+///     .clear_logo_key();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EpisodeUpdate {
+    title: Option<String>,
+    content: Option<String>,
+    status: Option<EpisodeStatus>,
+    episode_type: Option<EpisodeType>,
+    publish_time: Option<Timestamp>,
+    media_key: FieldUpdate<MediaKey>,
+    logo_key: FieldUpdate<MediaKey>,
+    transcripts_key: FieldUpdate<MediaKey>,
+    season_number: FieldUpdate<u32>,
+    episode_number: FieldUpdate<u32>,
+    apple_episode_type: FieldUpdate<AppleEpisodeType>,
+    content_explicit: FieldUpdate<ContentExplicit>,
+}
+
+impl EpisodeUpdate {
+    /// Creates an update that leaves every field unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a new title.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets new content or show notes.
+    pub fn content(mut self, content: &str) -> Self {
+        self.content = Some(content.to_string());
+        self
+    }
+
+    /// Sets a new publication status.
+    ///
+    /// [`EpisodeStatus::Future`] requires a
+    /// [`publish_time`](EpisodeUpdate::publish_time).
+    pub fn status(mut self, status: EpisodeStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Sets a new episode type.
+    pub fn episode_type(mut self, episode_type: EpisodeType) -> Self {
+        self.episode_type = Some(episode_type);
+        self
+    }
+
+    /// Sets a new publishing time.
+    ///
+    /// Accepts the same types as [`NewEpisode::publish_time`].
+    pub fn publish_time(mut self, time: impl Into<Timestamp>) -> Self {
+        self.publish_time = Some(time.into());
+        self
+    }
+
+    /// Replaces the episode media with a newly uploaded file.
+    pub fn media_key(mut self, media_key: &MediaKey) -> Self {
+        self.media_key = FieldUpdate::Set(media_key.clone());
+        self
+    }
+
+    /// Removes the episode media.
+    pub fn clear_media_key(mut self) -> Self {
+        self.media_key = FieldUpdate::Clear;
+        self
+    }
+
+    /// Replaces the episode logo with a newly uploaded file.
+    pub fn logo_key(mut self, logo_key: &MediaKey) -> Self {
+        self.logo_key = FieldUpdate::Set(logo_key.clone());
+        self
+    }
+
+    /// Removes the episode logo.
+    pub fn clear_logo_key(mut self) -> Self {
+        self.logo_key = FieldUpdate::Clear;
+        self
+    }
+
+    /// Replaces the transcript with a newly uploaded file.
+    pub fn transcripts_key(mut self, transcripts_key: &MediaKey) -> Self {
+        self.transcripts_key = FieldUpdate::Set(transcripts_key.clone());
+        self
+    }
+
+    /// Removes the transcript.
+    pub fn clear_transcripts_key(mut self) -> Self {
+        self.transcripts_key = FieldUpdate::Clear;
+        self
+    }
+
+    /// Sets a new season number, starting at one.
+    pub fn season_number(mut self, season_number: u32) -> Self {
+        self.season_number = FieldUpdate::Set(season_number);
+        self
+    }
+
+    /// Removes the season number.
+    pub fn clear_season_number(mut self) -> Self {
+        self.season_number = FieldUpdate::Clear;
+        self
+    }
+
+    /// Sets a new episode number, starting at one.
+    pub fn episode_number(mut self, episode_number: u32) -> Self {
+        self.episode_number = FieldUpdate::Set(episode_number);
+        self
+    }
+
+    /// Removes the episode number.
+    pub fn clear_episode_number(mut self) -> Self {
+        self.episode_number = FieldUpdate::Clear;
+        self
+    }
+
+    /// Sets a new episode type reported to Apple Podcasts.
+    pub fn apple_episode_type(mut self, apple_episode_type: AppleEpisodeType) -> Self {
+        self.apple_episode_type = FieldUpdate::Set(apple_episode_type);
+        self
+    }
+
+    /// Removes the episode type reported to Apple Podcasts.
+    pub fn clear_apple_episode_type(mut self) -> Self {
+        self.apple_episode_type = FieldUpdate::Clear;
+        self
+    }
+
+    /// Sets a new explicit content rating.
+    pub fn content_explicit(mut self, content_explicit: ContentExplicit) -> Self {
+        self.content_explicit = FieldUpdate::Set(content_explicit);
+        self
+    }
+
+    /// Removes the explicit content rating.
+    pub fn clear_content_explicit(mut self) -> Self {
+        self.content_explicit = FieldUpdate::Clear;
+        self
+    }
+
+    /// Checks that the update can be applied.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if every changed field is valid
+    /// * `Err(PodbeanError::ValidationError)` naming the first invalid field
+    pub fn validate(&self) -> PodbeanResult<()> {
+        if let Some(title) = &self.title {
+            validate_title(title)?;
+        }
+
+        if let Some(content) = &self.content
+            && content.trim().is_empty()
+        {
+            return Err(PodbeanError::validation("content", "must not be empty"));
+        }
+
+        if self.status == Some(EpisodeStatus::Future) && self.publish_time.is_none() {
+            return Err(PodbeanError::validation(
+                "publish_time",
+                "is required for future episodes",
+            ));
+        }
+
+        if self.season_number == FieldUpdate::Set(0) {
+            return Err(PodbeanError::validation(
+                "season_number",
+                "must be at least 1",
+            ));
+        }
+
+        if self.episode_number == FieldUpdate::Set(0) {
+            return Err(PodbeanError::validation(
+                "episode_number",
+                "must be at least 1",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Converts the update into request parameters for `episode_id`.
+    pub(crate) fn to_params(&self, episode_id: &EpisodeId) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+        let _ = params.insert("id".to_string(), episode_id.to_string());
+
+        let required = [
+            ("title", self.title.clone()),
+            ("content", self.content.clone()),
+            ("status", self.status.as_ref().map(|s| s.to_string())),
+            ("type", self.episode_type.as_ref().map(|t| t.to_string())),
+            (
+                "publish_timestamp",
+                self.publish_time.map(|t| t.unix_timestamp().to_string()),
+            ),
+        ];
+
+        for (key, value) in required {
+            if let Some(value) = value {
+                let _ = params.insert(key.to_string(), value);
+            }
+        }
+
+        self.media_key.insert_param(&mut params, "media_key");
+        self.logo_key.insert_param(&mut params, "logo_key");
+        self.transcripts_key
+            .insert_param(&mut params, "transcripts_key");
+        self.season_number
+            .insert_param(&mut params, "season_number");
+        self.episode_number
+            .insert_param(&mut params, "episode_number");
+        self.apple_episode_type
+            .insert_param(&mut params, "apple_episode_type");
+        self.content_explicit
+            .insert_param(&mut params, "content_explicit");
+
+        params
+    }
+}
+
+/// Checks that an episode title is present and not too long.
+fn validate_title(title: &str) -> PodbeanResult<()> {
+    if title.trim().is_empty() {
+        return Err(PodbeanError::validation("title", "must not be empty"));
+    }
+
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(PodbeanError::validation(
+            "title",
+            &format!("must not be longer than {} characters", MAX_TITLE_LENGTH),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn sends_the_required_fields_of_a_new_episode() {
+        let episode = NewEpisode::new(&PodcastId::from("pod"), "Pilot", "Notes");
+
+        assert_eq!(
+            episode.to_params(),
+            params(&[
+                ("podcast_id", "pod"),
+                ("title", "Pilot"),
+                ("content", "Notes"),
+                ("status", "draft"),
+                ("type", "public"),
+            ])
+        );
+    }
+
+    #[test]
+    fn sends_the_optional_fields_of_a_new_episode() {
+        let episode = NewEpisode::new(&PodcastId::from("pod"), "Pilot", "Notes")
+            .status(EpisodeStatus::Future)
+            .episode_type(EpisodeType::Premium)
+            .media_key(&MediaKey::from("audio.mp3"))
+            .logo_key(&MediaKey::from("logo.png"))
+            .transcripts_key(&MediaKey::from("transcript.srt"))
+            .publish_time(Timestamp::from_unix_timestamp(1_709_296_200))
+            .season_number(2)
+            .episode_number(14)
+            .apple_episode_type(AppleEpisodeType::Bonus)
+            .content_explicit(ContentExplicit::Clean);
+
+        assert_eq!(
+            episode.to_params(),
+            params(&[
+                ("podcast_id", "pod"),
+                ("title", "Pilot"),
+                ("content", "Notes"),
+                ("status", "future"),
+                ("type", "premium"),
+                ("media_key", "audio.mp3"),
+                ("logo_key", "logo.png"),
+                ("transcripts_key", "transcript.srt"),
+                ("publish_timestamp", "1709296200"),
+                ("season_number", "2"),
+                ("episode_number", "14"),
+                ("apple_episode_type", "bonus"),
+                ("content_explicit", "clean"),
+            ])
+        );
+    }
+
+    #[test]
+    fn validates_a_new_episode() {
+        let podcast_id = PodcastId::from("pod");
+        let field = |episode: NewEpisode| match episode.validate() {
+            Err(PodbeanError::ValidationError { field, .. }) => Some(field),
+            _ => None,
+        };
+
+        assert!(
+            NewEpisode::new(&podcast_id, "Pilot", "Notes")
+                .validate()
+                .is_ok()
+        );
+        assert_eq!(
+            field(NewEpisode::new(&PodcastId::from(" "), "Pilot", "Notes")).as_deref(),
+            Some("podcast_id")
+        );
+        assert_eq!(
+            field(NewEpisode::new(&podcast_id, &"x".repeat(201), "Notes")).as_deref(),
+            Some("title")
+        );
+        assert_eq!(
+            field(NewEpisode::new(&podcast_id, "Pilot", "")).as_deref(),
+            Some("content")
+        );
+        assert_eq!(
+            field(NewEpisode::new(&podcast_id, "Pilot", "Notes").status(EpisodeStatus::Future))
+                .as_deref(),
+            Some("publish_time")
+        );
+        assert_eq!(
+            field(NewEpisode::new(&podcast_id, "Pilot", "Notes").season_number(0)).as_deref(),
+            Some("season_number")
+        );
+    }
+
+    #[test]
+    fn sends_only_the_changed_fields_of_an_update() {
+        let episode_id = EpisodeId::from("ep");
+
+        assert_eq!(
+            EpisodeUpdate::new().to_params(&episode_id),
+            params(&[("id", "ep")])
+        );
+        assert_eq!(
+            EpisodeUpdate::new()
+                .title("Pilot")
+                .status(EpisodeStatus::Publish)
+                .publish_time(Timestamp::from_unix_timestamp(1_709_296_200))
+                .media_key(&MediaKey::from("audio.mp3"))
+                .season_number(2)
+                .to_params(&episode_id),
+            params(&[
+                ("id", "ep"),
+                ("title", "Pilot"),
+                ("status", "publish"),
+                ("publish_timestamp", "1709296200"),
+                ("media_key", "audio.mp3"),
+                ("season_number", "2"),
+            ])
+        );
+    }
+
+    #[test]
+    fn sends_cleared_fields_as_empty_values() {
+        let update = EpisodeUpdate::new()
+            .clear_media_key()
+            .clear_logo_key()
+            .clear_transcripts_key()
+            .clear_season_number()
+            .clear_episode_number()
+            .clear_apple_episode_type()
+            .clear_content_explicit();
+
+        assert_eq!(
+            update.to_params(&EpisodeId::from("ep")),
+            params(&[
+                ("id", "ep"),
+                ("media_key", ""),
+                ("logo_key", ""),
+                ("transcripts_key", ""),
+                ("season_number", ""),
+                ("episode_number", ""),
+                ("apple_episode_type", ""),
+                ("content_explicit", ""),
+            ])
+        );
+    }
+
+    #[test]
+    fn requires_a_publish_time_to_schedule_an_update() {
+        let scheduled = EpisodeUpdate::new().status(EpisodeStatus::Future);
+
+        assert!(matches!(
+            scheduled.clone().validate(),
+            Err(PodbeanError::ValidationError { field, .. }) if field == "publish_time"
+        ));
+        assert!(
+            scheduled
+                .publish_time(Timestamp::from_unix_timestamp(1_709_296_200))
+                .validate()
+                .is_ok()
+        );
+        assert!(EpisodeUpdate::new().validate().is_ok());
+    }
+}
@@ -3,19 +3,36 @@
 //! This module defines the various error types that can occur when
 //! interacting with the Podbean API.
 
+use crate::Scope;
+use reqwest::{Method, StatusCode};
 use std::error::Error;
 use std::fmt;
+use url::Url;
 
 /// Possible errors that can occur when using the Podbean API client.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use podbean::{ApiErrorKind, EpisodeId, PodbeanClient, PodbeanError};
+/// # use tokio::runtime::Runtime;
+/// # let client = PodbeanClient::new("id", "secret").unwrap();
+/// # let rt = Runtime::new().unwrap();
+/// # rt.block_on(async {
+/// match client.get_episode(&EpisodeId::from("episode_id")).await {
+///     Ok(episode) => println!("Found {}", episode.title),
+///     Err(PodbeanError::ApiError(e)) if e.kind == ApiErrorKind::NotFound => {
+///         println!("No such episode");
+///     }
+///     Err(e) if e.is_auth() => println!("Please sign in again: {}", e),
+///     Err(e) => eprintln!("Request failed: {}", e),
+/// }
+/// # });
+/// ```
 #[derive(Debug)]
 pub enum PodbeanError {
-    /// Error returned by the Podbean API.
-    ApiError {
-        /// HTTP status code
-        code: u16,
-        /// Error message
-        message: String,
-    },
+    /// Error response returned by the Podbean API.
+    ApiError(Box<ApiError>),
 
     /// Rate limit exceeded error.
     RateLimitError {
@@ -32,19 +49,245 @@ pub enum PodbeanError {
     /// Error parsing a URL.
     UrlParseError(url::ParseError),
 
+    /// I/O error, e.g. when reading or writing a persisted token.
+    IoError(std::io::Error),
+
     /// Authentication-related error.
     AuthError(String),
 
-    /// Any other type of error.
+    /// The user denied access in the authorization dialog, or Podbean
+    /// redirected back with another error.
+    AuthorizationDenied {
+        /// Error code from the redirect, e.g. `access_denied`
+        error: String,
+        /// Human-readable error description, if any
+        description: Option<String>,
+    },
+
+    /// A request was not sent because the access token lacks the scope it
+    /// requires, so the API would reject it.
+    MissingScope(Scope),
+
+    /// The API answered successfully, but with a response that is missing
+    /// expected data.
+    InvalidResponse(String),
+
+    /// The operation was cancelled through a cancellation token.
+    Cancelled,
+
+    /// A request was rejected before being sent because a field is invalid.
+    ValidationError {
+        /// Name of the invalid field
+        field: String,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Any other type of error, such as one raised by a custom
+    /// [`TokenStore`](crate::TokenStore).
     OtherError(String),
 }
 
+/// Category of an error response returned by the Podbean API.
+///
+/// Derived from the `error` code in the response body where Podbean sends
+/// one, and from the HTTP status otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The access token is missing, malformed, expired or revoked.
+    InvalidToken,
+
+    /// The client credentials, authorization code or refresh token were
+    /// rejected by the OAuth token endpoint, or access was denied.
+    InvalidGrant,
+
+    /// The access token does not grant the scope needed for the request,
+    /// or a requested scope is invalid.
+    InsufficientScope,
+
+    /// The requested podcast, episode or file does not exist.
+    NotFound,
+
+    /// A request parameter is missing or invalid.
+    InvalidRequest,
+
+    /// An account quota, such as storage or upload allowance, is used up.
+    QuotaExceeded,
+
+    /// The server failed to handle a valid request.
+    ServerError,
+
+    /// Any other error response.
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Classifies an error response by its `error` code and HTTP status.
+    fn classify(status: StatusCode, code: Option<&str>) -> Self {
+        match code.map(str::to_ascii_lowercase).as_deref() {
+            Some("invalid_token" | "expired_token" | "token_expired") => {
+                return ApiErrorKind::InvalidToken;
+            }
+            Some("invalid_grant" | "invalid_client" | "unauthorized_client" | "access_denied") => {
+                return ApiErrorKind::InvalidGrant;
+            }
+            Some("insufficient_scope" | "invalid_scope") => {
+                return ApiErrorKind::InsufficientScope;
+            }
+            Some("not_found") => return ApiErrorKind::NotFound,
+            Some(code) if code.contains("quota") => return ApiErrorKind::QuotaExceeded,
+            Some(code) if code.starts_with("invalid") || code.contains("param") => {
+                return ApiErrorKind::InvalidRequest;
+            }
+            _ => {}
+        }
+
+        match status {
+            StatusCode::UNAUTHORIZED => ApiErrorKind::InvalidToken,
+            StatusCode::FORBIDDEN => ApiErrorKind::InsufficientScope,
+            StatusCode::NOT_FOUND => ApiErrorKind::NotFound,
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
+                ApiErrorKind::InvalidRequest
+            }
+            StatusCode::PAYMENT_REQUIRED | StatusCode::INSUFFICIENT_STORAGE => {
+                ApiErrorKind::QuotaExceeded
+            }
+            status if status.is_server_error() => ApiErrorKind::ServerError,
+            _ => ApiErrorKind::Other,
+        }
+    }
+}
+
+/// An error response returned by the Podbean API.
+///
+/// Besides the parsed error, the request method, endpoint and raw response
+/// body are kept for debugging.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// Category of the error
+    pub kind: ApiErrorKind,
+
+    /// HTTP status code
+    pub status: u16,
+
+    /// Error code from the response body, e.g. `invalid_token`
+    pub code: Option<String>,
+
+    /// Human-readable error description
+    pub message: String,
+
+    /// Name of the invalid parameter, if the API reported one
+    pub field: Option<String>,
+
+    /// HTTP method of the failed request
+    pub method: Method,
+
+    /// URL of the endpoint, without the query string
+    pub endpoint: String,
+
+    /// Raw response body
+    pub body: String,
+}
+
+impl ApiError {
+    /// Parses an error response from the API.
+    ///
+    /// Podbean sends OAuth-style `error` and `error_description` fields;
+    /// bodies in any other shape are kept as the message.
+    pub(crate) fn new(status: StatusCode, method: Method, url: &Url, body: String) -> Self {
+        let json = serde_json::from_str::<serde_json::Value>(&body).ok();
+        let text = |key: &str| {
+            json.as_ref()
+                .and_then(|json| json.get(key))
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        };
+
+        let code = text("error");
+        let message = text("error_description")
+            .or_else(|| text("message"))
+            .unwrap_or_else(|| body.trim().to_string());
+        let field = text("field").or_else(|| text("param"));
+
+        let mut endpoint = url.clone();
+        endpoint.set_query(None);
+        endpoint.set_fragment(None);
+
+        Self {
+            kind: ApiErrorKind::classify(status, code.as_deref()),
+            status: status.as_u16(),
+            code,
+            message,
+            field,
+            method,
+            endpoint: endpoint.to_string(),
+            body,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "API error {}", self.status)?;
+
+        if let Some(code) = &self.code {
+            write!(f, " ({})", code)?;
+        }
+
+        write!(f, " on {} {}: {}", self.method, self.endpoint, self.message)?;
+
+        if let Some(field) = &self.field {
+            write!(f, " [field: {}]", field)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PodbeanError {
+    /// Checks if the failed request may succeed when sent again.
+    ///
+    /// True for timeouts and connection failures, rate limiting and server
+    /// errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PodbeanError::NetworkError(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            PodbeanError::ApiError(e) => e.kind == ApiErrorKind::ServerError,
+            PodbeanError::RateLimitError { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Checks if the error is caused by missing, invalid or insufficient
+    /// credentials, so that the user needs to authorize again.
+    pub fn is_auth(&self) -> bool {
+        match self {
+            PodbeanError::AuthError(_)
+            | PodbeanError::AuthorizationDenied { .. }
+            | PodbeanError::MissingScope(_) => true,
+            PodbeanError::ApiError(e) => matches!(
+                e.kind,
+                ApiErrorKind::InvalidToken
+                    | ApiErrorKind::InvalidGrant
+                    | ApiErrorKind::InsufficientScope
+            ),
+            _ => false,
+        }
+    }
+
+    /// Creates a validation error for `field`.
+    pub(crate) fn validation(field: &str, message: &str) -> Self {
+        PodbeanError::ValidationError {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
 impl fmt::Display for PodbeanError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            PodbeanError::ApiError { code, message } => {
-                write!(f, "API error {}: {}", code, message)
-            }
+            PodbeanError::ApiError(e) => e.fmt(f),
             PodbeanError::RateLimitError { retry_after } => {
                 if let Some(seconds) = retry_after {
                     write!(f, "Rate limit exceeded. Retry after {} seconds", seconds)
@@ -55,7 +298,25 @@ impl fmt::Display for PodbeanError {
             PodbeanError::NetworkError(e) => write!(f, "Network error: {}", e),
             PodbeanError::SerializationError(e) => write!(f, "Serialization error: {}", e),
             PodbeanError::UrlParseError(e) => write!(f, "URL parse error: {}", e),
+            PodbeanError::IoError(e) => write!(f, "I/O error: {}", e),
             PodbeanError::AuthError(msg) => write!(f, "Authentication error: {}", msg),
+            PodbeanError::AuthorizationDenied { error, description } => {
+                write!(f, "Authorization denied ({})", error)?;
+
+                if let Some(description) = description {
+                    write!(f, ": {}", description)?;
+                }
+
+                Ok(())
+            }
+            PodbeanError::MissingScope(scope) => {
+                write!(f, "Access token lacks the {} scope", scope)
+            }
+            PodbeanError::InvalidResponse(msg) => write!(f, "Invalid response: {}", msg),
+            PodbeanError::Cancelled => write!(f, "Operation cancelled"),
+            PodbeanError::ValidationError { field, message } => {
+                write!(f, "Invalid {}: {}", field, message)
+            }
             PodbeanError::OtherError(msg) => write!(f, "Error: {}", msg),
         }
     }
@@ -67,6 +328,7 @@ impl Error for PodbeanError {
             PodbeanError::NetworkError(e) => Some(e),
             PodbeanError::SerializationError(e) => Some(e),
             PodbeanError::UrlParseError(e) => Some(e),
+            PodbeanError::IoError(e) => Some(e),
             _ => None,
         }
     }
@@ -89,3 +351,54 @@ impl From<url::ParseError> for PodbeanError {
         PodbeanError::UrlParseError(err)
     }
 }
+
+impl From<std::io::Error> for PodbeanError {
+    fn from(err: std::io::Error) -> Self {
+        PodbeanError::IoError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classify(status: u16, code: &str) -> ApiErrorKind {
+        let status = StatusCode::from_u16(status).unwrap();
+        ApiErrorKind::classify(status, Some(code).filter(|code| !code.is_empty()))
+    }
+
+    #[test]
+    fn classifies_scope_errors() {
+        assert_eq!(
+            classify(403, "insufficient_scope"),
+            ApiErrorKind::InsufficientScope
+        );
+        assert_eq!(
+            classify(400, "invalid_scope"),
+            ApiErrorKind::InsufficientScope
+        );
+        assert_eq!(
+            classify(400, "INVALID_SCOPE"),
+            ApiErrorKind::InsufficientScope
+        );
+    }
+
+    #[test]
+    fn classifies_denied_access_as_an_auth_error() {
+        assert_eq!(classify(403, "access_denied"), ApiErrorKind::InvalidGrant);
+        assert_eq!(classify(401, "invalid_client"), ApiErrorKind::InvalidGrant);
+        assert_eq!(classify(401, "invalid_token"), ApiErrorKind::InvalidToken);
+    }
+
+    #[test]
+    fn falls_back_to_the_status_code() {
+        assert_eq!(
+            classify(400, "invalid_parameter"),
+            ApiErrorKind::InvalidRequest
+        );
+        assert_eq!(classify(403, ""), ApiErrorKind::InsufficientScope);
+        assert_eq!(classify(404, ""), ApiErrorKind::NotFound);
+        assert_eq!(classify(503, "unexpected"), ApiErrorKind::ServerError);
+        assert_eq!(classify(418, ""), ApiErrorKind::Other);
+    }
+}
@@ -0,0 +1,182 @@
+//! Authorization code flow for the Podbean API client.
+//!
+//! This module defines [`AuthorizationFlow`], which generates the `state`
+//! and PKCE values for a single login, builds the authorization dialog URL
+//! from them and checks the redirect before exchanging its code for a
+//! token.
+
+use crate::{AuthToken, PodbeanClient, PodbeanError, PodbeanResult, Scopes, SecretString};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+/// A single run of the OAuth authorization code flow.
+///
+/// Each flow carries a random `state`, checked when the user is redirected
+/// back to guard against cross-site request forgery, and a PKCE code
+/// verifier, whose hash is sent with the authorization request so that an
+/// intercepted code cannot be exchanged by anyone else.
+///
+/// Keep the flow between sending the user to
+/// [`authorization_url`](AuthorizationFlow::authorization_url) and handling
+/// the redirect with [`complete`](AuthorizationFlow::complete). It
+/// serializes, so web apps can store it in the user's session.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use podbean::{AuthorizationFlow, PodbeanClient};
+/// # use tokio::runtime::Runtime;
+/// # let client = PodbeanClient::new("id", "secret").unwrap();
+/// # let rt = Runtime::new().unwrap();
+/// # rt.block_on(async {
+/// let flow = AuthorizationFlow::new("https://your-app.com/callback").unwrap();
+/// println!("Visit this URL to authorize: {}", flow.authorization_url(&client).unwrap());
+///
+/// // Later, with the URL the browser was redirected to
+/// let callback = "https://your-app.com/callback?code=...&state=...";
+/// flow.complete(&client, callback).await.unwrap();
+/// # });
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationFlow {
+    redirect_uri: String,
+    state: String,
+    code_verifier: SecretString,
+    scopes: Scopes,
+}
+
+impl AuthorizationFlow {
+    /// Starts a flow redirecting back to `redirect_uri`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(AuthorizationFlow)` with a fresh state and code verifier
+    /// * `Err(PodbeanError)` if the system random number generator failed
+    pub fn new(redirect_uri: &str) -> PodbeanResult<Self> {
+        Ok(Self {
+            redirect_uri: redirect_uri.to_string(),
+            state: random_string(16)?,
+            code_verifier: SecretString::new(random_string(32)?),
+            scopes: Scopes::new(),
+        })
+    }
+
+    /// Requests specific scopes instead of the app's default ones.
+    pub fn scopes(mut self, scopes: Scopes) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Gets the URI the user is redirected to after authorization.
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// Gets the `state` expected back on the redirect.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Builds the URL of the authorization dialog to send the user to.
+    pub fn authorization_url(&self, client: &PodbeanClient) -> PodbeanResult<String> {
+        let url = client.get_authorization_url_with_scopes(
+            &self.redirect_uri,
+            Some(&self.state),
+            &self.scopes,
+        )?;
+        let mut url = Url::parse(&url)?;
+
+        let _ = url
+            .query_pairs_mut()
+            .append_pair(
+                "code_challenge",
+                &code_challenge(self.code_verifier.expose_secret()),
+            )
+            .append_pair("code_challenge_method", "S256");
+
+        Ok(url.to_string())
+    }
+
+    /// Extracts the authorization code from the URL the user was
+    /// redirected to.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(String)` containing the authorization code
+    /// * `Err(PodbeanError::AuthError)` if the URL cannot be parsed, its
+    ///   `state` does not match the flow, or it carries no code
+    /// * `Err(PodbeanError::AuthorizationDenied)` if the user denied access
+    ///   or Podbean reported another error
+    pub fn parse_callback(&self, callback_url: &str) -> PodbeanResult<String> {
+        let url = Url::parse(callback_url)
+            .map_err(|e| PodbeanError::AuthError(format!("Invalid callback URL: {}", e)))?;
+        let param = |key: &str| {
+            url.query_pairs()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.into_owned())
+        };
+
+        if param("state").as_deref() != Some(self.state.as_str()) {
+            return Err(PodbeanError::AuthError(
+                "Callback state does not match the authorization request".to_string(),
+            ));
+        }
+
+        if let Some(error) = param("error") {
+            return Err(PodbeanError::AuthorizationDenied {
+                error,
+                description: param("error_description"),
+            });
+        }
+
+        param("code")
+            .filter(|code| !code.is_empty())
+            .ok_or_else(|| PodbeanError::AuthError("Callback URL has no code".to_string()))
+    }
+
+    /// Checks the URL the user was redirected to and exchanges its code for
+    /// a token, which `client` then uses.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if authorization was successful
+    /// * `Err(PodbeanError)` if the callback is invalid or the exchange
+    ///   failed; see [`parse_callback`](AuthorizationFlow::parse_callback)
+    pub async fn complete(&self, client: &PodbeanClient, callback_url: &str) -> PodbeanResult<()> {
+        self.exchange(client, callback_url).await.map(|_| ())
+    }
+
+    /// Checks the callback and exchanges its code, returning the token.
+    pub(crate) async fn exchange(
+        &self,
+        client: &PodbeanClient,
+        callback_url: &str,
+    ) -> PodbeanResult<AuthToken> {
+        let code = self.parse_callback(callback_url)?;
+
+        client
+            .authorize_with_verifier(
+                &code,
+                &self.redirect_uri,
+                Some(self.code_verifier.expose_secret()),
+            )
+            .await
+    }
+}
+
+/// Derives the S256 PKCE code challenge of `code_verifier`.
+pub(crate) fn code_challenge(code_verifier: &str) -> String {
+    BASE64_URL.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// Generates `len` random bytes, encoded as URL-safe base64.
+fn random_string(len: usize) -> PodbeanResult<String> {
+    let mut bytes = vec![0; len];
+    getrandom::fill(&mut bytes)
+        .map_err(|e| PodbeanError::OtherError(format!("Failed to generate random bytes: {}", e)))?;
+
+    Ok(BASE64_URL.encode(bytes))
+}
@@ -0,0 +1,120 @@
+//! Identifier types for Podbean API resources.
+//!
+//! This module defines [`PodcastId`], [`EpisodeId`] and [`MediaKey`].
+//! They are all strings on the wire, but distinct types in Rust, so that
+//! passing one where another is expected fails to compile.
+
+use crate::{PodbeanError, PodbeanResult};
+use percent_encoding::{AsciiSet, CONTROLS, PercentEncode, utf8_percent_encode};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Characters escaped in an identifier placed in a URL path segment.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Percent-encodes an identifier for use as a single URL path segment, so
+/// that characters such as `/` or `?` cannot change the endpoint called.
+///
+/// Empty identifiers and the dot segments `.` and `..`, which URL parsing
+/// would remove or resolve, are rejected as invalid `field` values.
+pub(crate) fn path_segment<'a>(field: &str, id: &'a str) -> PodbeanResult<PercentEncode<'a>> {
+    if matches!(id, "" | "." | "..") {
+        return Err(PodbeanError::validation(
+            field,
+            "must be a non-empty ID other than `.` or `..`",
+        ));
+    }
+
+    Ok(utf8_percent_encode(id, PATH_SEGMENT))
+}
+
+/// Defines a string identifier newtype.
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Wraps an identifier returned by the Podbean API.
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            /// Gets the identifier as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+    };
+}
+
+id_type! {
+    /// The ID of a podcast.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use podbean::PodcastId;
+    ///
+    /// let podcast_id = PodcastId::from("pb12345");
+    /// assert_eq!(podcast_id.as_str(), "pb12345");
+    /// ```
+    PodcastId
+}
+
+id_type! {
+    /// The ID of an episode.
+    EpisodeId
+}
+
+id_type! {
+    /// The key of an uploaded file, returned by
+    /// [`PodbeanClient::upload_media`](crate::PodbeanClient::upload_media).
+    ///
+    /// Used to attach media, logos and transcripts to episodes and podcasts.
+    MediaKey
+}
@@ -31,20 +31,85 @@
     unused_mut
 )]
 
-use reqwest::{Client, Response, StatusCode};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use futures_core::Stream;
+use pagination::paginate;
+use rate_limit::RateLimiter;
+use reqwest::StatusCode;
+use reqwest::header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, HeaderMap, HeaderValue};
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::fmt;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
-use types::AuthToken;
+use tokio::io::AsyncRead;
+use tokio::sync::{Mutex, MutexGuard};
+use tokio_util::io::ReaderStream;
 use url::Url;
 
+mod builder;
+pub use builder::PodbeanClientBuilder;
+
+mod episode;
+pub use episode::{EpisodeUpdate, FieldUpdate, NewEpisode};
+
 mod error;
-pub use error::PodbeanError;
+pub use error::{ApiError, ApiErrorKind, PodbeanError};
+
+mod flow;
+pub use flow::AuthorizationFlow;
+
+mod id;
+pub use id::{EpisodeId, MediaKey, PodcastId};
+
+#[cfg(feature = "loopback")]
+mod loopback;
+#[cfg(feature = "loopback")]
+pub use loopback::LoopbackLogin;
+
+#[cfg(feature = "mock-server")]
+mod mock;
+#[cfg(feature = "mock-server")]
+pub use mock::{MockFault, MockServer, RecordedRequest};
+
+mod pagination;
+
+mod podcast;
+pub use podcast::PodcastUpdate;
+
+mod rate_limit;
+pub use rate_limit::RateLimit;
+
+mod retry;
+pub use retry::RetryPolicy;
+
+mod scope;
+pub use scope::{Scope, Scopes};
+
+mod store;
+pub use store::{BoxFuture, FileTokenStore, MemoryTokenStore, TokenStore};
+
+mod upload;
+pub use tokio_util::sync::CancellationToken;
+use upload::ProgressStream;
+pub use upload::{UploadOptions, UploadProgress};
+
+mod timestamp;
+pub use timestamp::Timestamp;
+
+mod transport;
+pub use transport::{
+    BodyStream, HttpRequest, HttpResponse, RequestBody, ReqwestTransport, Transport,
+};
 
 mod types;
 pub use types::{
-    Episode, EpisodeListResponse, EpisodeStatus, EpisodeType, MediaFormat, MediaItem,
-    MediaListResponse, PodcastListResponse, TokenResponse,
+    AppleEpisodeType, AuthToken, ContentExplicit, Episode, EpisodeListResponse, EpisodeStatus,
+    EpisodeType, Grant, MediaFormat, MediaItem, MediaListResponse, MediaStatus, Podcast,
+    PodcastListResponse, SecretString, TokenInfo, TokenResponse,
 };
 
 /// Result type for Podbean API operations.
@@ -54,13 +119,47 @@ pub type PodbeanResult<T> = Result<T, PodbeanError>;
 ///
 /// This client handles authentication, token management, rate limiting,
 /// and provides methods to interact with Podbean's API endpoints.
-#[derive(Debug, Clone)]
+///
+/// All methods take `&self`, so a client can be shared across tasks behind
+/// an `Arc`. Clones share the same token state: when several tasks find the
+/// token expired at once, a single refresh request is sent and the others
+/// wait for its result.
+///
+/// Accounts managing several podcasts can additionally obtain a token per
+/// podcast with [`authorize_podcasts`](PodbeanClient::authorize_podcasts).
+/// Calls naming a podcast then use its token.
+#[derive(Clone)]
 pub struct PodbeanClient {
-    client: Client,
+    transport: Arc<dyn Transport>,
     client_id: String,
-    client_secret: String,
+    client_secret: SecretString,
     base_url: String,
-    token: Option<AuthToken>,
+    token_url: String,
+    dialog_url: String,
+    token: Arc<Mutex<Option<AuthToken>>>,
+    podcast_tokens: Arc<Mutex<HashMap<PodcastId, AuthToken>>>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    rate_limiter: Option<RateLimiter>,
+    retry_policy: RetryPolicy,
+    upload_timeout: Duration,
+}
+
+impl fmt::Debug for PodbeanClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The secret is redacted by `SecretString`, and token state is left out.
+        f.debug_struct("PodbeanClient")
+            .field("transport", &self.transport)
+            .field("client_id", &self.client_id)
+            .field("client_secret", &self.client_secret)
+            .field("base_url", &self.base_url)
+            .field("token_url", &self.token_url)
+            .field("dialog_url", &self.dialog_url)
+            .field("token_store", &self.token_store)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("retry_policy", &self.retry_policy)
+            .field("upload_timeout", &self.upload_timeout)
+            .finish_non_exhaustive()
+    }
 }
 
 impl PodbeanClient {
@@ -79,15 +178,49 @@ impl PodbeanClient {
     /// let client = PodbeanClient::new("your_client_id", "your_client_secret");
     /// ```
     pub fn new(client_id: &str, client_secret: &str) -> Result<Self, PodbeanError> {
-        let client = Client::builder().timeout(Duration::from_secs(30)).build()?;
-
-        Ok(Self {
-            client,
-            client_id: client_id.to_string(),
-            client_secret: client_secret.to_string(),
-            base_url: "https://api.podbean.com/v1".to_string(),
-            token: None,
-        })
+        Self::builder(client_id, client_secret).build()
+    }
+
+    /// Creates a builder for configuring a Podbean API client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - The client ID from your Podbean API application
+    /// * `client_secret` - The client secret from your Podbean API application
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use podbean::PodbeanClient;
+    /// use std::time::Duration;
+    ///
+    /// let client = PodbeanClient::builder("your_client_id", "your_client_secret")
+    ///     .timeout(Duration::from_secs(60))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(client_id: &str, client_secret: &str) -> PodbeanClientBuilder {
+        PodbeanClientBuilder::new(client_id, client_secret)
+    }
+
+    /// Persists tokens with the given store.
+    ///
+    /// The client loads the saved token the first time it needs one and
+    /// saves every token it obtains afterwards, so authorization survives
+    /// process restarts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use podbean::{FileTokenStore, PodbeanClient};
+    ///
+    /// let client = PodbeanClient::new("your_client_id", "your_client_secret")
+    ///     .unwrap()
+    ///     .with_token_store(FileTokenStore::new("podbean-token.json"));
+    /// ```
+    pub fn with_token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(Arc::new(store));
+        self
     }
 
     /// Authorize the client using an authorization code.
@@ -100,6 +233,10 @@ impl PodbeanClient {
     /// * `code` - The authorization code received after user authorization
     /// * `redirect_uri` - The redirect URI used in the authorization request
     ///
+    /// If a [`TokenStore`] is configured and saving the new token to it
+    /// fails, the token is still used and `Ok` is returned; the failure is
+    /// logged as a `tracing` warning.
+    ///
     /// # Returns
     ///
     /// * `Ok(())` if authorization was successful
@@ -110,7 +247,7 @@ impl PodbeanClient {
     /// ```no_run
     /// # use podbean::PodbeanClient;
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// let code = "authorization_code"; // From callback URL
@@ -122,23 +259,130 @@ impl PodbeanClient {
     /// }
     /// });
     /// ```
-    pub async fn authorize(&mut self, code: &str, redirect_uri: &str) -> PodbeanResult<()> {
-        let params = [
-            ("grant_type", "client_credentials"),
+    pub async fn authorize(&self, code: &str, redirect_uri: &str) -> PodbeanResult<()> {
+        self.authorize_with_verifier(code, redirect_uri, None)
+            .await
+            .map(|_| ())
+    }
+
+    /// Exchanges an authorization code for a token, sending the PKCE code
+    /// verifier if the authorization request carried a challenge.
+    pub(crate) async fn authorize_with_verifier(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+        code_verifier: Option<&str>,
+    ) -> PodbeanResult<AuthToken> {
+        let mut params = vec![
+            ("grant_type", "authorization_code"),
             ("code", code),
             ("redirect_uri", redirect_uri),
-            ("client_id", &self.client_id),
-            ("client_secret", &self.client_secret),
         ];
 
-        let response = self
-            .client
-            .post("https://api.podbean.com/v1/oauth/token")
-            .form(&params)
-            .send()
+        if let Some(code_verifier) = code_verifier {
+            params.push(("code_verifier", code_verifier));
+        }
+
+        let token = self
+            .request_token(&params, Grant::AuthorizationCode)
             .await?;
 
-        self.handle_token_response(response).await
+        self.set_token(&mut *self.token.lock().await, token.clone())
+            .await;
+
+        Ok(token)
+    }
+
+    /// Authorize the client using the client credentials grant.
+    ///
+    /// This method obtains an app-level access token from only the client ID
+    /// and client secret, without any user interaction. Use it for backend
+    /// jobs that never go through the browser-based authorization flow.
+    ///
+    /// Tokens obtained this way are renewed automatically with the same
+    /// grant once they expire.
+    ///
+    /// If a [`TokenStore`] is configured and saving the new token to it
+    /// fails, the token is still used and `Ok` is returned; the failure is
+    /// logged as a `tracing` warning.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if authorization was successful
+    /// * `Err(PodbeanError)` if there was an error during authorization
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::PodbeanClient;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// match client.authorize_client_credentials().await {
+    ///     Ok(_) => println!("Authorization successful!"),
+    ///     Err(e) => eprintln!("Authorization failed: {}", e),
+    /// }
+    /// # });
+    /// ```
+    pub async fn authorize_client_credentials(&self) -> PodbeanResult<()> {
+        let token = self.request_client_credentials_token().await?;
+
+        self.set_token(&mut *self.token.lock().await, token).await;
+
+        Ok(())
+    }
+
+    /// Obtains a token for every podcast the app credentials can manage.
+    ///
+    /// Podbean issues podcast-scoped tokens, and network accounts can
+    /// exchange one set of client credentials for tokens on several
+    /// podcasts. The tokens are cached by podcast ID, and calls naming a
+    /// podcast, such as [`publish_episode`](PodbeanClient::publish_episode),
+    /// [`list_episodes`](PodbeanClient::list_episodes),
+    /// [`get_podcast`](PodbeanClient::get_podcast) and
+    /// [`update_podcast`](PodbeanClient::update_podcast), use the matching
+    /// token automatically. Calls that do not name a podcast, and calls for
+    /// podcasts without a token, use the token obtained with
+    /// [`authorize`](PodbeanClient::authorize) or
+    /// [`authorize_client_credentials`](PodbeanClient::authorize_client_credentials).
+    /// Without such a token, calls that do not name a podcast, such as
+    /// [`upload_media`](PodbeanClient::upload_media) or
+    /// [`get_episode`](PodbeanClient::get_episode), use the podcast token if
+    /// exactly one was obtained.
+    ///
+    /// Podcast tokens are renewed automatically once they expire. They are
+    /// not persisted by the [`TokenStore`].
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<PodcastId>)` listing the podcasts a token was obtained for
+    /// * `Err(PodbeanError)` if there was an error during authorization
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::PodbeanClient;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// let podcasts = client.authorize_podcasts().await.unwrap();
+    ///
+    /// for podcast_id in &podcasts {
+    ///     let episodes = client.list_episodes(Some(podcast_id), None, None).await.unwrap();
+    ///     println!("{}: {} episodes", podcast_id, episodes.count);
+    /// }
+    /// # });
+    /// ```
+    pub async fn authorize_podcasts(&self) -> PodbeanResult<Vec<PodcastId>> {
+        let mut tokens = self.podcast_tokens.lock().await;
+        *tokens = self.request_podcast_tokens().await?;
+
+        let mut podcast_ids: Vec<PodcastId> = tokens.keys().cloned().collect();
+        podcast_ids.sort();
+
+        Ok(podcast_ids)
     }
 
     /// Refresh the access token.
@@ -146,6 +390,10 @@ impl PodbeanClient {
     /// This method uses the refresh token to obtain a new access token
     /// when the current one expires.
     ///
+    /// If a [`TokenStore`] is configured and saving the new token to it
+    /// fails, the token is still used and `Ok` is returned; the failure is
+    /// logged as a `tracing` warning.
+    ///
     /// # Returns
     ///
     /// * `Ok(())` if token refresh was successful
@@ -156,7 +404,7 @@ impl PodbeanClient {
     /// ```no_run
     /// # use podbean::PodbeanClient;
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// // Typically called automatically by the client when needed
@@ -166,64 +414,436 @@ impl PodbeanClient {
     /// }
     /// # });
     /// ```
-    pub async fn refresh_token(&mut self) -> PodbeanResult<()> {
-        if let Some(token) = &self.token
-            && let Some(refresh_token) = token.refresh_token() {
-                let params = [
-                    ("grant_type", "refresh_token"),
-                    ("refresh_token", refresh_token),
-                    ("client_id", &self.client_id),
-                    ("client_secret", &self.client_secret),
-                ];
-
-                let response = self
-                    .client
-                    .post("https://api.podbean.com/v1/oauth/token")
-                    .form(&params)
-                    .send()
-                    .await?;
-
-                return self.handle_token_response(response).await;
+    pub async fn refresh_token(&self) -> PodbeanResult<()> {
+        let mut slot = self.token_slot().await?;
+
+        match slot.as_ref() {
+            Some(token) if token.refresh_token().is_some() => {
+                let token = self.refresh_access_token(token).await?;
+
+                self.set_token(&mut slot, token).await;
+
+                Ok(())
             }
+            _ => Err(PodbeanError::AuthError(
+                "No refresh token available".to_string(),
+            )),
+        }
+    }
 
-        Err(PodbeanError::AuthError(
-            "No refresh token available".to_string(),
-        ))
+    /// Gets details of an access token from Podbean.
+    ///
+    /// Reports whether the token is still valid, which podcast and scopes it
+    /// grants access to, and when it expires. The token does not need to be
+    /// one held by this client, but it must have been issued to the same
+    /// app. It is sent in the request body, so it stays out of URLs and
+    /// the logs that record them.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_token` - The access token to inspect
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TokenInfo)` describing the token
+    /// * `Err(PodbeanError)` if the request failed or the token is unknown
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::PodbeanClient;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// let info = client.debug_token("access_token_of_a_customer").await.unwrap();
+    ///
+    /// if info.is_valid {
+    ///     println!("Token for {:?} expires at {:?}", info.podcast_id, info.expires_at);
+    /// }
+    /// # });
+    /// ```
+    pub async fn debug_token(&self, access_token: &str) -> PodbeanResult<TokenInfo> {
+        let mut url = self.oauth_url("debugToken")?;
+        let _ = url
+            .query_pairs_mut()
+            .append_pair("access_token", access_token);
+
+        let response = self
+            .send_client_request(reqwest::Method::GET, &url, RequestBody::Empty)
+            .await?;
+
+        Ok(serde_json::from_slice(&response.body)?)
     }
 
-    /// Handles the token response from authorization or refresh requests.
-    async fn handle_token_response(&mut self, response: Response) -> PodbeanResult<()> {
-        if response.status().is_success() {
-            let token_response: TokenResponse = response.json().await?;
+    /// Gets details of the client's current access token from Podbean.
+    ///
+    /// See [`debug_token`](PodbeanClient::debug_token).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(TokenInfo)` describing the token
+    /// * `Err(PodbeanError)` if the client is not authorized or the request
+    ///   failed
+    pub async fn introspect_token(&self) -> PodbeanResult<TokenInfo> {
+        let token = self.ensure_token(None).await?;
+
+        self.debug_token(token.access_token()).await
+    }
 
-            self.token = Some(AuthToken::from(token_response));
+    /// Revokes the client's tokens and forgets them.
+    ///
+    /// The access and refresh tokens held by the client, including the
+    /// podcast tokens obtained with
+    /// [`authorize_podcasts`](PodbeanClient::authorize_podcasts), are
+    /// revoked with Podbean. Once all of them are revoked, they are dropped
+    /// from memory and the [`TokenStore`] is cleared, so the client must be
+    /// authorized again before further calls. Tokens Podbean already
+    /// considers invalid count as revoked.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if every token was revoked
+    /// * `Err(PodbeanError)` if a token could not be revoked, in which case
+    ///   the local state is kept so the call can be retried
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::PodbeanClient;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// // Offboarding a customer
+    /// client.revoke_token().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn revoke_token(&self) -> PodbeanResult<()> {
+        let mut slot = self.token_slot().await?;
+        let mut podcast_tokens = self.podcast_tokens.lock().await;
 
-            Ok(())
+        for token in slot.iter().chain(podcast_tokens.values()) {
+            self.revoke(token.access_token()).await?;
+
+            if let Some(refresh_token) = token.refresh_token() {
+                self.revoke(refresh_token).await?;
+            }
+        }
+
+        *slot = None;
+        podcast_tokens.clear();
+
+        if let Some(store) = &self.token_store {
+            store.clear().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Revokes a single access or refresh token.
+    async fn revoke(&self, token: &str) -> PodbeanResult<()> {
+        let url = self.oauth_url("revokeToken")?;
+        let params = vec![("token".to_string(), token.to_string())];
+
+        match self
+            .send_client_request(reqwest::Method::POST, &url, RequestBody::Form(params))
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(PodbeanError::ApiError(error))
+                if matches!(
+                    error.kind,
+                    ApiErrorKind::InvalidToken | ApiErrorKind::InvalidGrant
+                ) =>
+            {
+                Ok(())
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Sends a request to an OAuth endpoint, authenticated with the client
+    /// credentials through HTTP Basic authentication.
+    ///
+    /// Every OAuth endpoint is called this way, so the client secret never
+    /// appears in a request body or URL.
+    async fn send_client_request(
+        &self,
+        method: reqwest::Method,
+        url: &Url,
+        body: RequestBody,
+    ) -> PodbeanResult<HttpResponse> {
+        let credentials = format!("{}:{}", self.client_id, self.client_secret.expose_secret());
+        let mut authorization =
+            HeaderValue::from_str(&format!("Basic {}", BASE64.encode(credentials)))
+                .map_err(|_| PodbeanError::AuthError("Invalid client credentials".to_string()))?;
+        authorization.set_sensitive(true);
+
+        let mut headers = HeaderMap::new();
+        let _ = headers.insert(AUTHORIZATION, authorization);
+
+        let request = HttpRequest {
+            method: method.clone(),
+            url: url.clone(),
+            headers,
+            body,
+            timeout: None,
+        };
+        let response = self.transport.send(request).await?;
+
+        if response.status.is_success() {
+            Ok(response)
         } else {
-            Err(self.handle_error_response(response).await)
+            Err(self.handle_error_response(method, url, response))
         }
     }
 
-    /// Ensures a valid token is available, refreshing if necessary.
-    async fn ensure_token(&self) -> PodbeanResult<()> {
-        if let Some(token) = &self.token {
-            if token.is_expired() {
+    /// Builds the URL of an OAuth endpoint other than the token endpoint.
+    ///
+    /// The endpoints are siblings of the configured token endpoint, e.g.
+    /// `.../oauth/debugToken` next to `.../oauth/token`.
+    fn oauth_url(&self, endpoint: &str) -> PodbeanResult<Url> {
+        Ok(Url::parse(&self.token_url)?.join(endpoint)?)
+    }
+
+    /// Exchanges the refresh token of `token` for a new access token.
+    async fn refresh_access_token(&self, token: &AuthToken) -> PodbeanResult<AuthToken> {
+        let refresh_token = token
+            .refresh_token()
+            .ok_or_else(|| PodbeanError::AuthError("No refresh token available".to_string()))?;
+        let params = [
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ];
+
+        let mut refreshed = self.request_token(&params, token.grant()).await?;
+        refreshed.inherit(token);
+
+        Ok(refreshed)
+    }
+
+    /// Replaces the token held in `slot` with a new one.
+    ///
+    /// A refresh token is preferred when present; tokens obtained through
+    /// the client credentials grant are otherwise requested again. The
+    /// caller holds the token lock, so concurrent callers wait for this
+    /// renewal instead of issuing their own.
+    async fn renew_token(&self, slot: &mut Option<AuthToken>) -> PodbeanResult<AuthToken> {
+        let token = match slot.as_ref() {
+            Some(token) if token.refresh_token().is_some() => {
+                self.refresh_access_token(token).await?
+            }
+            Some(token) if token.grant() == Grant::ClientCredentials => {
+                self.request_client_credentials_token().await?
+            }
+            Some(_) => {
                 return Err(PodbeanError::AuthError(
-                    "Refresh authentication token".to_string(),
+                    "Authentication token expired and cannot be refreshed".to_string(),
                 ));
             }
-            Ok(())
-        } else {
-            Err(PodbeanError::AuthError("Not authenticated".to_string()))
+            None => return Err(PodbeanError::AuthError("Not authenticated".to_string())),
+        };
+
+        self.set_token(slot, token.clone()).await;
+
+        Ok(token)
+    }
+
+    /// Locks the token state, restoring a persisted token if none is held.
+    async fn token_slot(&self) -> PodbeanResult<MutexGuard<'_, Option<AuthToken>>> {
+        let mut slot = self.token.lock().await;
+
+        if slot.is_none()
+            && let Some(store) = &self.token_store
+        {
+            *slot = store.load().await?;
+        }
+
+        Ok(slot)
+    }
+
+    /// Stores a newly obtained token, persisting it if a store is configured.
+    ///
+    /// The token is used even if saving it fails, since obtaining it may
+    /// have used up the authorization code or the previous refresh token.
+    /// The failure is logged as a warning instead of being returned.
+    async fn set_token(&self, slot: &mut Option<AuthToken>, token: AuthToken) {
+        let token = slot.insert(token);
+
+        if let Some(store) = &self.token_store
+            && let Err(error) = store.save(token).await
+        {
+            tracing::warn!(%error, "failed to save the new token, keeping it in memory only");
+        }
+    }
+
+    /// Requests a token for every podcast the client credentials can manage.
+    async fn request_podcast_tokens(&self) -> PodbeanResult<HashMap<PodcastId, AuthToken>> {
+        let url = self.oauth_url("multiplePodcastsToken")?;
+        let params = vec![("grant_type".to_string(), "client_credentials".to_string())];
+
+        let response = self
+            .send_client_request(reqwest::Method::POST, &url, RequestBody::Form(params))
+            .await?;
+        let response: PodcastTokensResponse = serde_json::from_slice(&response.body)?;
+
+        Ok(response
+            .podcasts
+            .into_iter()
+            .map(|podcast| {
+                let token = AuthToken::new(podcast.token, Grant::ClientCredentials)
+                    .for_podcast(podcast.podcast_id.clone());
+                (podcast.podcast_id, token)
+            })
+            .collect())
+    }
+
+    /// Replaces every podcast token and returns the one for `podcast_id`.
+    ///
+    /// The caller holds the podcast token lock, so concurrent callers wait
+    /// for this renewal instead of issuing their own.
+    async fn renew_podcast_token(
+        &self,
+        tokens: &mut HashMap<PodcastId, AuthToken>,
+        podcast_id: &PodcastId,
+    ) -> PodbeanResult<AuthToken> {
+        *tokens = self.request_podcast_tokens().await?;
+
+        tokens.get(podcast_id).cloned().ok_or_else(|| {
+            PodbeanError::AuthError(format!("No token issued for podcast {}", podcast_id))
+        })
+    }
+
+    /// Requests an app-level token using the client credentials grant.
+    async fn request_client_credentials_token(&self) -> PodbeanResult<AuthToken> {
+        self.request_token(
+            &[("grant_type", "client_credentials")],
+            Grant::ClientCredentials,
+        )
+        .await
+    }
+
+    /// Sends a grant request to the OAuth token endpoint.
+    async fn request_token(
+        &self,
+        grant_params: &[(&str, &str)],
+        grant: Grant,
+    ) -> PodbeanResult<AuthToken> {
+        let params = grant_params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        let url = Url::parse(&self.token_url)?;
+        let response = self
+            .send_client_request(reqwest::Method::POST, &url, RequestBody::Form(params))
+            .await?;
+        let token_response: TokenResponse = serde_json::from_slice(&response.body)?;
+
+        Ok(AuthToken::new(token_response, grant))
+    }
+
+    /// Returns a valid token, refreshing it first if it has expired.
+    ///
+    /// The token of `podcast_id` is used if one was obtained with
+    /// [`authorize_podcasts`](PodbeanClient::authorize_podcasts).
+    async fn ensure_token(&self, podcast_id: Option<&PodcastId>) -> PodbeanResult<AuthToken> {
+        if let Some(podcast_id) = podcast_id {
+            let mut tokens = self.podcast_tokens.lock().await;
+
+            match tokens.get(podcast_id) {
+                Some(token) if token.is_expired() => {
+                    return self.renew_podcast_token(&mut tokens, podcast_id).await;
+                }
+                Some(token) => return Ok(token.clone()),
+                None => {}
+            }
+        }
+
+        let mut slot = self.token_slot().await?;
+
+        match slot.as_ref() {
+            Some(token) if token.is_expired() => self.renew_token(&mut slot).await,
+            Some(token) => Ok(token.clone()),
+            None => {
+                drop(slot);
+                self.sole_podcast_token().await
+            }
         }
     }
 
+    /// Returns the podcast token to use for calls that do not name a
+    /// podcast when only podcast tokens were obtained.
+    ///
+    /// This is only unambiguous if a single podcast token is cached.
+    async fn sole_podcast_token(&self) -> PodbeanResult<AuthToken> {
+        let mut tokens = self.podcast_tokens.lock().await;
+
+        let podcast_id = match tokens.keys().next() {
+            Some(podcast_id) if tokens.len() == 1 => podcast_id.clone(),
+            Some(_) => {
+                return Err(PodbeanError::AuthError(
+                    "Not authenticated: several podcast tokens are cached and the call \
+                     does not name a podcast"
+                        .to_string(),
+                ));
+            }
+            None => return Err(PodbeanError::AuthError("Not authenticated".to_string())),
+        };
+
+        match tokens.get(&podcast_id) {
+            Some(token) if !token.is_expired() => Ok(token.clone()),
+            _ => self.renew_podcast_token(&mut tokens, &podcast_id).await,
+        }
+    }
+
+    /// Returns a token to retry with after `rejected` was refused by the API.
+    ///
+    /// If another task already replaced the rejected token, that token is
+    /// reused rather than refreshing a second time.
+    async fn renew_rejected_token(&self, rejected: &AuthToken) -> PodbeanResult<AuthToken> {
+        if let Some(podcast_id) = rejected.podcast_id() {
+            let mut tokens = self.podcast_tokens.lock().await;
+
+            return match tokens.get(podcast_id) {
+                Some(token) if token.access_token() != rejected.access_token() => Ok(token.clone()),
+                _ => self.renew_podcast_token(&mut tokens, podcast_id).await,
+            };
+        }
+
+        let mut slot = self.token_slot().await?;
+
+        match slot.as_ref() {
+            Some(token) if token.access_token() != rejected.access_token() => Ok(token.clone()),
+            _ => self.renew_token(&mut slot).await,
+        }
+    }
+
+    /// Makes a request to the Podbean API with the default token.
+    async fn make_request<T>(
+        &self,
+        method: reqwest::Method,
+        endpoint: &str,
+        params: Option<HashMap<String, String>>,
+    ) -> PodbeanResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        self.make_podcast_request(None, method, endpoint, params)
+            .await
+    }
+
     /// Makes a request to the Podbean API.
     ///
     /// This internal method handles token management, rate limiting,
-    /// and error handling for all API requests.
-    async fn make_request<T>(
+    /// retries and error handling for all API requests. Failed attempts
+    /// are retried according to the client's [`RetryPolicy`]. Requests
+    /// concerning `podcast_id` use its token when one is cached.
+    /// Requests needing a scope the token lacks fail without being sent.
+    async fn make_podcast_request<T>(
         &self,
+        podcast_id: Option<&PodcastId>,
         method: reqwest::Method,
         endpoint: &str,
         params: Option<HashMap<String, String>>,
@@ -231,68 +851,152 @@ impl PodbeanClient {
     where
         T: for<'de> Deserialize<'de>,
     {
-        self.ensure_token().await?;
+        let url = Url::parse(&format!("{}{}", self.base_url, endpoint))?;
+        let scope = Scope::required_for(&method, endpoint);
+        let mut attempt = 1;
+
+        loop {
+            let error = match self
+                .attempt_request(podcast_id, scope.as_ref(), &method, &url, params.as_ref())
+                .await
+            {
+                Ok(result) => return Ok(result),
+                Err(error) => error,
+            };
 
-        let url = format!("{}{}", self.base_url, endpoint);
-        let token = self.token.as_ref().unwrap();
+            match self.retry_policy.retry_delay(&method, attempt, &error) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(error),
+            }
 
-        let mut request_builder = self.client.request(method.clone(), &url).header(
-            "Authorization",
-            format!("{} {}", token.token_type(), token.access_token()),
-        );
+            attempt += 1;
+        }
+    }
 
-        if let Some(params) = params {
-            request_builder = if method == reqwest::Method::GET {
-                request_builder.query(&params)
-            } else {
-                request_builder.form(&params)
-            };
+    /// Makes a single attempt at a request to the Podbean API.
+    ///
+    /// An expired token is refreshed before the request is sent, and a
+    /// `401 Unauthorized` response triggers a single refresh-and-retry.
+    async fn attempt_request<T>(
+        &self,
+        podcast_id: Option<&PodcastId>,
+        scope: Option<&Scope>,
+        method: &reqwest::Method,
+        url: &Url,
+        params: Option<&HashMap<String, String>>,
+    ) -> PodbeanResult<T>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let token = self.ensure_token(podcast_id).await?;
+
+        if let Some(scope) = scope
+            && !token.has_scope(scope)
+        {
+            return Err(PodbeanError::MissingScope(scope.clone()));
         }
 
-        let response = request_builder.send().await?;
+        let mut response = self.send_request(method, url, params, &token).await?;
 
-        if response.status().is_success() {
-            let result: T = response.json().await?;
+        if response.status == StatusCode::UNAUTHORIZED && token.is_renewable() {
+            let token = self.renew_rejected_token(&token).await?;
+            response = self.send_request(method, url, params, &token).await?;
+        }
+
+        if response.status.is_success() {
+            let result: T = serde_json::from_slice(&response.body)?;
             Ok(result)
         } else {
-            Err(self.handle_error_response(response).await)
+            Err(self.handle_error_response(method.clone(), url, response))
         }
     }
 
-    /// Processes error responses from the API.
-    async fn handle_error_response(&self, response: Response) -> PodbeanError {
-        let status = response.status();
-
-        if status == StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = response
-                .headers()
-                .get("Retry-After")
-                .and_then(|h| h.to_str().ok())
-                .and_then(|s| s.parse::<u64>().ok());
-
-            return PodbeanError::RateLimitError { retry_after };
-        }
-
-        match response.text().await {
-            Ok(text) => {
-                if let Ok(error_json) = serde_json::from_str::<serde_json::Value>(&text)
-                    && let (Some(error), Some(message)) = (
-                        error_json.get("error").and_then(|v| v.as_str()),
-                        error_json.get("error_description").and_then(|v| v.as_str()),
-                    ) {
-                        return PodbeanError::ApiError {
-                            code: status.as_u16(),
-                            message: format!("{}: {}", error, message),
-                        };
-                    }
-
-                PodbeanError::ApiError {
-                    code: status.as_u16(),
-                    message: text,
+    /// Sends a single authenticated request to the API.
+    async fn send_request(
+        &self,
+        method: &reqwest::Method,
+        url: &Url,
+        params: Option<&HashMap<String, String>>,
+        token: &AuthToken,
+    ) -> PodbeanResult<HttpResponse> {
+        let authorization = format!("{} {}", token.token_type(), token.access_token());
+        let mut authorization = HeaderValue::try_from(authorization).map_err(|_| {
+            PodbeanError::InvalidResponse("Access token is not a valid header".to_string())
+        })?;
+        authorization.set_sensitive(true);
+
+        let mut headers = HeaderMap::new();
+        let _ = headers.insert(AUTHORIZATION, authorization);
+
+        let mut url = url.clone();
+        let mut body = RequestBody::Empty;
+
+        if let Some(params) = params {
+            if method == reqwest::Method::GET {
+                // An empty `query_pairs_mut` would leave a bare `?`
+                if !params.is_empty() {
+                    let _ = url.query_pairs_mut().extend_pairs(params);
                 }
+            } else {
+                body = RequestBody::Form(
+                    params
+                        .iter()
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect(),
+                );
             }
-            Err(e) => PodbeanError::OtherError(format!("Failed to read error response: {}", e)),
         }
+
+        let request = HttpRequest {
+            method: method.clone(),
+            url,
+            headers,
+            body,
+            timeout: None,
+        };
+
+        self.send_rate_limited(request).await
+    }
+
+    /// Sends a request once the rate limiter allows it.
+    ///
+    /// `429 Too Many Requests` responses slow the limiter down so later
+    /// requests back off before the server rejects them.
+    async fn send_rate_limited(&self, request: HttpRequest) -> PodbeanResult<HttpResponse> {
+        let Some(limiter) = &self.rate_limiter else {
+            return self.transport.send(request).await;
+        };
+
+        limiter.acquire().await;
+        let response = self.transport.send(request).await?;
+
+        if response.status == StatusCode::TOO_MANY_REQUESTS {
+            limiter.record_rate_limited(retry_after(&response));
+        } else {
+            limiter.record_success();
+        }
+
+        Ok(response)
+    }
+
+    /// Processes error responses from the API.
+    ///
+    /// `method` and `url` are those of the request that `response` answers.
+    fn handle_error_response(
+        &self,
+        method: reqwest::Method,
+        url: &Url,
+        response: HttpResponse,
+    ) -> PodbeanError {
+        if response.status == StatusCode::TOO_MANY_REQUESTS {
+            return PodbeanError::RateLimitError {
+                retry_after: retry_after(&response),
+            };
+        }
+
+        let body = String::from_utf8_lossy(&response.body).into_owned();
+
+        PodbeanError::ApiError(Box::new(ApiError::new(response.status, method, url, body)))
     }
 
     /// Uploads a media file to Podbean.
@@ -300,14 +1004,20 @@ impl PodbeanClient {
     /// This method uploads a media file (typically an audio file) to Podbean
     /// and returns a media key that can be used to publish episodes.
     ///
+    /// The whole file is held in memory; prefer
+    /// [`upload_media_from_path`](PodbeanClient::upload_media_from_path) or
+    /// [`upload_media_from_reader`](PodbeanClient::upload_media_from_reader)
+    /// for large files.
+    ///
     /// # Arguments
     ///
-    /// * `file_path` - Path to the local file to upload
-    /// * `content_type` - MIME type of the file (e.g., "audio/mp3" for MP3)
+    /// * `file_name` - Name of the file as it should appear in Podbean
+    /// * `file_content` - Contents of the file
+    /// * `media_format` - Format of the file
     ///
     /// # Returns
     ///
-    /// * `Ok(String)` containing the media key if successful
+    /// * `Ok(MediaKey)` containing the media key if successful
     /// * `Err(PodbeanError)` if there was an error during upload
     ///
     /// # Examples
@@ -315,7 +1025,7 @@ impl PodbeanClient {
     /// ```no_run
     /// # use podbean::{PodbeanClient, MediaFormat};
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
@@ -328,114 +1038,277 @@ impl PodbeanClient {
         file_name: String,
         file_content: Vec<u8>,
         media_format: MediaFormat,
-    ) -> PodbeanResult<String> {
-        self.ensure_token().await?;
+    ) -> PodbeanResult<MediaKey> {
+        let file_size = file_content.len() as u64;
+
+        self.upload_media_from_reader(
+            file_name,
+            Cursor::new(file_content),
+            file_size,
+            media_format,
+        )
+        .await
+    }
 
+    /// Uploads a media file from the local file system to Podbean.
+    ///
+    /// The file is streamed to Podbean rather than read into memory, so
+    /// this is the preferred way to upload large episodes. The file name
+    /// and size are taken from the path and the file's metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the local file to upload
+    /// * `media_format` - Format of the file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MediaKey)` containing the media key if successful
+    /// * `Err(PodbeanError)` if there was an error during upload
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::{PodbeanClient, MediaFormat};
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let media_key = client.upload_media_from_path("episode.mp3", MediaFormat::Mp3).await.unwrap();
+    /// println!("Media uploaded with key: {}", media_key);
+    /// # });
+    /// ```
+    pub async fn upload_media_from_path(
+        &self,
+        path: impl AsRef<Path>,
+        media_format: MediaFormat,
+    ) -> PodbeanResult<MediaKey> {
+        self.upload_media_from_path_with(path, media_format, UploadOptions::default())
+            .await
+    }
+
+    /// Uploads a media file from the local file system to Podbean with
+    /// progress reporting, cancellation or a custom timeout.
+    ///
+    /// See [`upload_media_from_path`](PodbeanClient::upload_media_from_path)
+    /// and [`UploadOptions`].
+    pub async fn upload_media_from_path_with(
+        &self,
+        path: impl AsRef<Path>,
+        media_format: MediaFormat,
+        options: UploadOptions,
+    ) -> PodbeanResult<MediaKey> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| PodbeanError::validation("path", "must name a UTF-8 file"))?
+            .to_string();
+
+        let file = tokio::fs::File::open(path).await?;
+        let file_size = file.metadata().await?.len();
+
+        self.upload_media_from_reader_with(file_name, file, file_size, media_format, options)
+            .await
+    }
+
+    /// Uploads a media file to Podbean by streaming it from a reader.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - Name of the file as it should appear in Podbean
+    /// * `reader` - Reader producing exactly `file_size` bytes of media
+    /// * `file_size` - Size of the media in bytes
+    /// * `media_format` - Format of the file
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(MediaKey)` containing the media key if successful
+    /// * `Err(PodbeanError)` if there was an error during upload
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::{PodbeanClient, MediaFormat};
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let file = tokio::fs::File::open("episode.mp3").await.unwrap();
+    /// let file_size = file.metadata().await.unwrap().len();
+    ///
+    /// let media_key = client
+    ///     .upload_media_from_reader("episode.mp3".to_string(), file, file_size, MediaFormat::Mp3)
+    ///     .await
+    ///     .unwrap();
+    /// println!("Media uploaded with key: {}", media_key);
+    /// # });
+    /// ```
+    pub async fn upload_media_from_reader<R>(
+        &self,
+        file_name: String,
+        reader: R,
+        file_size: u64,
+        media_format: MediaFormat,
+    ) -> PodbeanResult<MediaKey>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        self.upload_media_from_reader_with(
+            file_name,
+            reader,
+            file_size,
+            media_format,
+            UploadOptions::default(),
+        )
+        .await
+    }
+
+    /// Uploads a media file to Podbean by streaming it from a reader with
+    /// progress reporting, cancellation or a custom timeout.
+    ///
+    /// See [`upload_media_from_reader`](PodbeanClient::upload_media_from_reader)
+    /// and [`UploadOptions`].
+    pub async fn upload_media_from_reader_with<R>(
+        &self,
+        file_name: String,
+        reader: R,
+        file_size: u64,
+        media_format: MediaFormat,
+        options: UploadOptions,
+    ) -> PodbeanResult<MediaKey>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let upload = self.upload_media_stream(file_name, reader, file_size, media_format, &options);
+
+        match &options.cancellation_token {
+            Some(token) => token
+                .run_until_cancelled(upload)
+                .await
+                .unwrap_or(Err(PodbeanError::Cancelled)),
+            None => upload.await,
+        }
+    }
+
+    /// Authorizes an upload and streams the file to the presigned URL.
+    async fn upload_media_stream<R>(
+        &self,
+        file_name: String,
+        reader: R,
+        file_size: u64,
+        media_format: MediaFormat,
+        options: &UploadOptions,
+    ) -> PodbeanResult<MediaKey>
+    where
+        R: AsyncRead + Send + 'static,
+    {
         // First, get the presigned URL for upload
         let mut params = HashMap::new();
 
         let _ = params.insert("filename".to_string(), file_name);
         let _ = params.insert("content_type".to_string(), media_format.to_string());
-        let _ = params.insert("filesize".to_string(), file_content.len().to_string());
+        let _ = params.insert("filesize".to_string(), file_size.to_string());
 
         let presigned: serde_json::Value = self
-            .make_request(reqwest::Method::GET, "/files/uploadAuthorize", Some(params))
+            .make_podcast_request(
+                options.podcast_id.as_ref(),
+                reqwest::Method::GET,
+                "/files/uploadAuthorize",
+                Some(params),
+            )
             .await?;
 
         let presigned_url = presigned["presigned_url"].as_str().ok_or_else(|| {
-            PodbeanError::OtherError("Missing presigned_url in response".to_string())
+            PodbeanError::InvalidResponse("Missing presigned_url in response".to_string())
         })?;
 
-        let file_key = presigned["file_key"]
-            .as_str()
-            .ok_or_else(|| PodbeanError::OtherError("Missing file_key in response".to_string()))?;
+        let file_key = presigned["file_key"].as_str().ok_or_else(|| {
+            PodbeanError::InvalidResponse("Missing file_key in response".to_string())
+        })?;
 
-        let upload_response = self
-            .client
-            .put(presigned_url)
-            .header("Content-Type", media_format.to_string())
-            .body(file_content)
-            .send()
-            .await?;
+        let presigned_url = Url::parse(presigned_url)?;
 
-        if !upload_response.status().is_success() {
-            return Err(self.handle_error_response(upload_response).await);
+        let chunks = Box::pin(ReaderStream::new(reader));
+        let body: BodyStream = match &options.progress {
+            Some(callback) => Box::pin(ProgressStream::new(chunks, file_size, callback.clone())),
+            None => chunks,
+        };
+
+        // Presigned URLs reject chunked uploads, so the length is always sent.
+        let mut headers = HeaderMap::new();
+        let _ = headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(media_format.content_type()),
+        );
+        let _ = headers.insert(CONTENT_LENGTH, HeaderValue::from(file_size));
+
+        let upload_request = HttpRequest {
+            method: reqwest::Method::PUT,
+            url: presigned_url.clone(),
+            headers,
+            body: RequestBody::Stream(body),
+            timeout: Some(options.timeout.unwrap_or(self.upload_timeout)),
+        };
+        let upload_response = self.send_rate_limited(upload_request).await?;
+
+        if !upload_response.status.is_success() {
+            return Err(self.handle_error_response(
+                reqwest::Method::PUT,
+                &presigned_url,
+                upload_response,
+            ));
         }
 
-        Ok(file_key.to_string())
+        Ok(MediaKey::from(file_key))
     }
 
     /// Publishes a new episode to a podcast.
     ///
+    /// The episode is validated before anything is sent, so missing or
+    /// malformed required fields fail without a network round trip.
+    ///
     /// # Arguments
     ///
-    /// * `podcast_id` - The ID of the podcast to publish to
-    /// * `title` - The title of the episode
-    /// * `content` - The description or show notes for the episode
-    /// * `media_key` - The media key returned from `upload_media`
-    /// * `status` - Publication status: "publish", "draft", or "future"
-    /// * `type` - Publication status: "public", "premium", "private"
-    /// * `publish_timestamp` - The publishing timestamp of an episode. The episode will be listed based on its publishing time, from New to Old by default. If it is not set, the "current time" will be set as its publishing time.
+    /// * `episode` - The episode to create, see [`NewEpisode`]
     ///
     /// # Returns
     ///
-    /// * `Ok(String)` containing the episode ID if successful
-    /// * `Err(PodbeanError)` if there was an error
+    /// * `Ok(Episode)` containing the created episode if successful
+    /// * `Err(PodbeanError)` if the episode is invalid or there was an error
     ///
     /// # Examples
     ///
-    /// ```no_run
-    /// # use podbean::{PodbeanClient, EpisodeStatus, EpisodeType};
-    /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
-    /// # let rt = Runtime::new().unwrap();
-    /// # rt.block_on(async {
-    /// # client.authorize("code", "redirect").await.unwrap();
-    /// # let media_key = "media_key";
-    /// let episode_id = client.publish_episode(
-    ///     "podcast_id",
-    ///     "My New Episode",
-    ///     "Episode description and show notes...",
-    ///     &media_key,
-    ///     EpisodeStatus::Draft,
-    ///     EpisodeType::Public,
-    ///     None,
-    /// ).await.unwrap();
-    ///
-    /// println!("Episode published with ID: {}", episode_id);
-    /// # });
-    /// ```
-    #[allow(clippy::too_many_arguments)]
-    pub async fn publish_episode(
-        &self,
-        podcast_id: &str,
-        title: &str,
-        content: &str,
-        media_key: &str,
-        status: EpisodeStatus,
-        episode_type: EpisodeType,
-        publish_timestamp: Option<i64>,
-    ) -> PodbeanResult<String> {
-        let mut params = HashMap::new();
-        let _ = params.insert("podcast_id".to_string(), podcast_id.to_string());
-        let _ = params.insert("title".to_string(), title.to_string());
-        let _ = params.insert("content".to_string(), content.to_string());
-        let _ = params.insert("media_key".to_string(), media_key.to_string());
-        let _ = params.insert("status".to_string(), status.to_string()); // publish, draft, future
-        let _ = params.insert("type".to_string(), episode_type.to_string()); // "public", "premium", "private"
-
-        if let Some(timestamp) = publish_timestamp {
-            let _ = params.insert("publish_timestamp".to_string(), timestamp.to_string());
-        }
-
-        let response: serde_json::Value = self
-            .make_request(reqwest::Method::POST, "/episodes", Some(params))
-            .await?;
-
-        response["episode"]["id"]
-            .as_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| PodbeanError::OtherError("Missing episode ID in response".to_string()))
+    /// ```no_run
+    /// # use podbean::{EpisodeStatus, MediaKey, NewEpisode, PodbeanClient, PodcastId};
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// # let media_key = MediaKey::from("media_key");
+    /// let podcast_id = PodcastId::from("podcast_id");
+    /// let episode = NewEpisode::new(&podcast_id, "My New Episode", "Episode description and show notes...")
+    ///     .media_key(&media_key)
+    ///     .status(EpisodeStatus::Draft);
+    ///
+    /// let episode = client.publish_episode(&episode).await.unwrap();
+    /// println!("Episode published with ID: {}", episode.id);
+    /// # });
+    /// ```
+    pub async fn publish_episode(&self, episode: &NewEpisode) -> PodbeanResult<Episode> {
+        episode.validate()?;
+
+        self.make_podcast_request::<EpisodeResponse>(
+            Some(episode.podcast_id()),
+            reqwest::Method::POST,
+            "/episodes",
+            Some(episode.to_params()),
+        )
+        .await
+        .map(|r| r.episode)
     }
 
     /// Gets information about a specific episode.
@@ -452,26 +1325,49 @@ impl PodbeanClient {
     /// # Examples
     ///
     /// ```no_run
-    /// # use podbean::PodbeanClient;
+    /// # use podbean::{EpisodeId, PodbeanClient};
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
-    /// let episode = client.get_episode("episode_id").await.unwrap();
+    /// let episode = client.get_episode(&EpisodeId::from("episode_id")).await.unwrap();
     /// println!("Episode title: {}", episode.title);
     /// println!("Listen URL: {}", episode.player_url);
     /// # });
     /// ```
-    pub async fn get_episode(&self, episode_id: &str) -> PodbeanResult<Episode> {
-        #[derive(Debug, Deserialize)]
-        struct EpisodeResponse {
-            episode: Episode,
-        }
+    pub async fn get_episode(&self, episode_id: &EpisodeId) -> PodbeanResult<Episode> {
+        self.fetch_episode(None, episode_id).await
+    }
+
+    /// Gets information about an episode of a specific podcast.
+    ///
+    /// Like [`get_episode`](PodbeanClient::get_episode), but uses the token
+    /// of `podcast_id` if one was obtained with
+    /// [`authorize_podcasts`](PodbeanClient::authorize_podcasts).
+    pub async fn get_episode_for(
+        &self,
+        podcast_id: &PodcastId,
+        episode_id: &EpisodeId,
+    ) -> PodbeanResult<Episode> {
+        self.fetch_episode(Some(podcast_id), episode_id).await
+    }
+
+    /// Gets an episode, with the token of `podcast_id` if given.
+    async fn fetch_episode(
+        &self,
+        podcast_id: Option<&PodcastId>,
+        episode_id: &EpisodeId,
+    ) -> PodbeanResult<Episode> {
+        let endpoint = format!(
+            "/episodes/{}",
+            id::path_segment("episode_id", episode_id.as_str())?
+        );
 
-        self.make_request::<EpisodeResponse>(
+        self.make_podcast_request::<EpisodeResponse>(
+            podcast_id,
             reqwest::Method::GET,
-            &format!("/episodes/{}", episode_id),
+            &endpoint,
             None,
         )
         .await
@@ -494,28 +1390,29 @@ impl PodbeanClient {
     /// # Examples
     ///
     /// ```no_run
-    /// # use podbean::PodbeanClient;
+    /// # use podbean::{PodbeanClient, PodcastId};
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
     /// // Get the first 10 episodes from a specific podcast
+    /// let podcast_id = PodcastId::from("podcast_id");
     /// let episodes = client.list_episodes(
-    ///     Some("podcast_id"),
+    ///     Some(&podcast_id),
     ///     None,  // Start from beginning
     ///     Some(10) // Get 10 episodes
     /// ).await.unwrap();
     ///
     /// println!("Found {} episodes", episodes.count);
     /// for episode in episodes.episodes {
-    ///     println!("- {} ({})", episode.title, episode.publish_time);
+    ///     println!("- {} ({:?})", episode.title, episode.publish_time);
     /// }
     /// # });
     /// ```
     pub async fn list_episodes(
         &self,
-        podcast_id: Option<&str>,
+        podcast_id: Option<&PodcastId>,
         offset: Option<u32>,
         limit: Option<u32>,
     ) -> PodbeanResult<EpisodeListResponse> {
@@ -533,77 +1430,126 @@ impl PodbeanClient {
             let _ = params.insert("limit".to_string(), limit_val.to_string());
         }
 
-        self.make_request(reqwest::Method::GET, "/episodes", Some(params))
+        self.make_podcast_request(podcast_id, reqwest::Method::GET, "/episodes", Some(params))
             .await
     }
 
+    /// Streams every episode, fetching pages lazily.
+    ///
+    /// Pages of `page_size` episodes are requested with
+    /// [`list_episodes`](PodbeanClient::list_episodes) as the stream is
+    /// consumed, until all `count` episodes have been yielded.
+    ///
+    /// # Arguments
+    ///
+    /// * `podcast_id` - Optional podcast ID to filter episodes
+    /// * `page_size` - Number of episodes to request per page
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::{PodbeanClient, PodcastId};
+    /// # use futures_util::TryStreamExt;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let podcast_id = PodcastId::from("podcast_id");
+    /// let mut episodes = std::pin::pin!(client.episodes_stream(Some(&podcast_id), 50));
+    ///
+    /// while let Some(item) = episodes.try_next().await.unwrap() {
+    ///     println!("- {}", item.title);
+    /// }
+    /// # });
+    /// ```
+    pub fn episodes_stream<'a>(
+        &'a self,
+        podcast_id: Option<&'a PodcastId>,
+        page_size: u32,
+    ) -> impl Stream<Item = PodbeanResult<Episode>> + 'a {
+        paginate(page_size, move |offset, limit| async move {
+            let page = self
+                .list_episodes(podcast_id, Some(offset), Some(limit))
+                .await?;
+            Ok((page.count, page.episodes))
+        })
+    }
+
     /// Updates an existing episode.
     ///
+    /// Only the fields set on `update` are changed.
+    ///
     /// # Arguments
     ///
     /// * `episode_id` - The ID of the episode to update
-    /// * `title` - Optional new title
-    /// * `content` - Optional new content/description
-    /// * `status` - Optional new status
-    /// * `publish_timestamp` - Optional new publication timestamp
+    /// * `update` - The changes to apply, see [`EpisodeUpdate`]
     ///
     /// # Returns
     ///
-    /// * `Ok(())` if update was successful
-    /// * `Err(PodbeanError)` if there was an error
+    /// * `Ok(Episode)` containing the updated episode if successful
+    /// * `Err(PodbeanError)` if the update is invalid or there was an error
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// # use podbean::PodbeanClient;
+    /// # use podbean::{EpisodeId, EpisodeUpdate, PodbeanClient};
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
     /// // Update just the title of an episode
-    /// client.update_episode(
-    ///     "episode_id",
-    ///     Some("Updated Title"),
-    ///     None,  // Keep current content
-    ///     None,  // Keep current status
-    ///     None   // Keep current publish time
-    /// ).await.unwrap();
-    /// println!("Episode updated successfully");
+    /// let episode = client
+    ///     .update_episode(
+    ///         &EpisodeId::from("episode_id"),
+    ///         &EpisodeUpdate::new().title("Updated Title"),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    /// println!("Episode updated: {}", episode.title);
     /// # });
     /// ```
     pub async fn update_episode(
         &self,
-        episode_id: &str,
-        title: Option<&str>,
-        content: Option<&str>,
-        status: Option<&str>,
-        publish_timestamp: Option<i64>,
-    ) -> PodbeanResult<()> {
-        let mut params = HashMap::new();
-        let _ = params.insert("id".to_string(), episode_id.to_string());
-
-        if let Some(title_val) = title {
-            let _ = params.insert("title".to_string(), title_val.to_string());
-        }
-
-        if let Some(content_val) = content {
-            let _ = params.insert("content".to_string(), content_val.to_string());
-        }
-
-        if let Some(status_val) = status {
-            let _ = params.insert("status".to_string(), status_val.to_string());
-        }
-
-        if let Some(timestamp) = publish_timestamp {
-            let _ = params.insert("publish_timestamp".to_string(), timestamp.to_string());
-        }
+        episode_id: &EpisodeId,
+        update: &EpisodeUpdate,
+    ) -> PodbeanResult<Episode> {
+        self.apply_episode_update(None, episode_id, update).await
+    }
 
-        let _: serde_json::Value = self
-            .make_request(reqwest::Method::PUT, "/episodes", Some(params))
-            .await?;
+    /// Updates an episode of a specific podcast.
+    ///
+    /// Like [`update_episode`](PodbeanClient::update_episode), but uses the
+    /// token of `podcast_id` if one was obtained with
+    /// [`authorize_podcasts`](PodbeanClient::authorize_podcasts).
+    pub async fn update_episode_for(
+        &self,
+        podcast_id: &PodcastId,
+        episode_id: &EpisodeId,
+        update: &EpisodeUpdate,
+    ) -> PodbeanResult<Episode> {
+        self.apply_episode_update(Some(podcast_id), episode_id, update)
+            .await
+    }
 
-        Ok(())
+    /// Updates an episode, with the token of `podcast_id` if given.
+    async fn apply_episode_update(
+        &self,
+        podcast_id: Option<&PodcastId>,
+        episode_id: &EpisodeId,
+        update: &EpisodeUpdate,
+    ) -> PodbeanResult<Episode> {
+        update.validate()?;
+
+        self.make_podcast_request::<EpisodeResponse>(
+            podcast_id,
+            reqwest::Method::PUT,
+            "/episodes",
+            Some(update.to_params(episode_id)),
+        )
+        .await
+        .map(|r| r.episode)
     }
 
     /// Deletes an episode.
@@ -620,27 +1566,140 @@ impl PodbeanClient {
     /// # Examples
     ///
     /// ```no_run
-    /// # use podbean::PodbeanClient;
+    /// # use podbean::{EpisodeId, PodbeanClient};
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
-    /// client.delete_episode("episode_id").await.unwrap();
+    /// client.delete_episode(&EpisodeId::from("episode_id")).await.unwrap();
     /// println!("Episode deleted successfully");
     /// # });
     /// ```
-    pub async fn delete_episode(&self, episode_id: &str) -> PodbeanResult<()> {
+    pub async fn delete_episode(&self, episode_id: &EpisodeId) -> PodbeanResult<()> {
+        self.remove_episode(None, episode_id).await
+    }
+
+    /// Deletes an episode of a specific podcast.
+    ///
+    /// Like [`delete_episode`](PodbeanClient::delete_episode), but uses the
+    /// token of `podcast_id` if one was obtained with
+    /// [`authorize_podcasts`](PodbeanClient::authorize_podcasts).
+    pub async fn delete_episode_for(
+        &self,
+        podcast_id: &PodcastId,
+        episode_id: &EpisodeId,
+    ) -> PodbeanResult<()> {
+        self.remove_episode(Some(podcast_id), episode_id).await
+    }
+
+    /// Deletes an episode, with the token of `podcast_id` if given.
+    async fn remove_episode(
+        &self,
+        podcast_id: Option<&PodcastId>,
+        episode_id: &EpisodeId,
+    ) -> PodbeanResult<()> {
         let mut params = HashMap::new();
         let _ = params.insert("id".to_string(), episode_id.to_string());
 
         let _: serde_json::Value = self
-            .make_request(reqwest::Method::DELETE, "/episodes", Some(params))
+            .make_podcast_request(
+                podcast_id,
+                reqwest::Method::DELETE,
+                "/episodes",
+                Some(params),
+            )
             .await?;
 
         Ok(())
     }
 
+    /// Gets information about a specific podcast.
+    ///
+    /// # Arguments
+    ///
+    /// * `podcast_id` - The ID of the podcast to retrieve
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Podcast)` containing the podcast details if successful
+    /// * `Err(PodbeanError)` if there was an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::{PodbeanClient, PodcastId};
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let podcast = client.get_podcast(&PodcastId::from("podcast_id")).await.unwrap();
+    /// println!("Podcast title: {}", podcast.title);
+    /// # });
+    /// ```
+    pub async fn get_podcast(&self, podcast_id: &PodcastId) -> PodbeanResult<Podcast> {
+        let endpoint = format!(
+            "/podcasts/{}",
+            id::path_segment("podcast_id", podcast_id.as_str())?
+        );
+
+        self.make_podcast_request::<PodcastResponse>(
+            Some(podcast_id),
+            reqwest::Method::GET,
+            &endpoint,
+            None,
+        )
+        .await
+        .map(|r| r.podcast)
+    }
+
+    /// Updates the settings of a podcast.
+    ///
+    /// Only the fields set on `update` are changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `podcast_id` - The ID of the podcast to update
+    /// * `update` - The changes to apply, see [`PodcastUpdate`]
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Podcast)` containing the updated podcast if successful
+    /// * `Err(PodbeanError)` if the update is invalid or there was an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::{PodbeanClient, PodcastId, PodcastUpdate};
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let update = PodcastUpdate::new().description("A weekly show about Rust");
+    /// let podcast_id = PodcastId::from("podcast_id");
+    /// let podcast = client.update_podcast(&podcast_id, &update).await.unwrap();
+    /// println!("Podcast updated: {}", podcast.title);
+    /// # });
+    /// ```
+    pub async fn update_podcast(
+        &self,
+        podcast_id: &PodcastId,
+        update: &PodcastUpdate,
+    ) -> PodbeanResult<Podcast> {
+        update.validate()?;
+
+        self.make_podcast_request::<PodcastResponse>(
+            Some(podcast_id),
+            reqwest::Method::PUT,
+            "/podcasts",
+            Some(update.to_params(podcast_id)),
+        )
+        .await
+        .map(|r| r.podcast)
+    }
+
     /// Lists podcasts for the authenticated user.
     ///
     /// # Arguments
@@ -658,7 +1717,7 @@ impl PodbeanClient {
     /// ```no_run
     /// # use podbean::PodbeanClient;
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
@@ -688,6 +1747,43 @@ impl PodbeanClient {
             .await
     }
 
+    /// Streams every podcast, fetching pages lazily.
+    ///
+    /// Pages of `page_size` podcasts are requested with
+    /// [`list_podcasts`](PodbeanClient::list_podcasts) as the stream is
+    /// consumed, until all `count` podcasts have been yielded.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_size` - Number of podcasts to request per page
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::PodbeanClient;
+    /// # use futures_util::TryStreamExt;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let mut podcasts = std::pin::pin!(client.podcasts_stream(20));
+    ///
+    /// while let Some(item) = podcasts.try_next().await.unwrap() {
+    ///     println!("- {}", item.title);
+    /// }
+    /// # });
+    /// ```
+    pub fn podcasts_stream(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = PodbeanResult<Podcast>> + '_ {
+        paginate(page_size, move |offset, limit| async move {
+            let page = self.list_podcasts(Some(offset), Some(limit)).await?;
+            Ok((page.count, page.podcasts))
+        })
+    }
+
     /// Lists media files for the authenticated user.
     ///
     /// # Arguments
@@ -705,7 +1801,7 @@ impl PodbeanClient {
     /// ```no_run
     /// # use podbean::PodbeanClient;
     /// # use tokio::runtime::Runtime;
-    /// # let mut client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
     /// # let rt = Runtime::new().unwrap();
     /// # rt.block_on(async {
     /// # client.authorize("code", "redirect").await.unwrap();
@@ -735,10 +1831,50 @@ impl PodbeanClient {
             .await
     }
 
+    /// Streams every media file, fetching pages lazily.
+    ///
+    /// Pages of `page_size` media files are requested with
+    /// [`list_media`](PodbeanClient::list_media) as the stream is
+    /// consumed, until all `count` media files have been yielded.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_size` - Number of media files to request per page
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use podbean::PodbeanClient;
+    /// # use futures_util::TryStreamExt;
+    /// # use tokio::runtime::Runtime;
+    /// # let client = PodbeanClient::new("id", "secret").unwrap();
+    /// # let rt = Runtime::new().unwrap();
+    /// # rt.block_on(async {
+    /// # client.authorize("code", "redirect").await.unwrap();
+    /// let mut media = std::pin::pin!(client.media_stream(50));
+    ///
+    /// while let Some(item) = media.try_next().await.unwrap() {
+    ///     println!("- {}", item.media_key);
+    /// }
+    /// # });
+    /// ```
+    pub fn media_stream(
+        &self,
+        page_size: u32,
+    ) -> impl Stream<Item = PodbeanResult<MediaItem>> + '_ {
+        paginate(page_size, move |offset, limit| async move {
+            let page = self.list_media(Some(offset), Some(limit)).await?;
+            Ok((page.count, page.media))
+        })
+    }
+
     /// Generates an authorization URL for OAuth2 flow.
     ///
     /// Users need to visit this URL to authorize your application to
-    /// access their Podbean account.
+    /// access their Podbean account. Podbean grants the app's default
+    /// scopes; use
+    /// [`get_authorization_url_with_scopes`](PodbeanClient::get_authorization_url_with_scopes)
+    /// to request specific ones.
     ///
     /// # Arguments
     ///
@@ -768,7 +1904,41 @@ impl PodbeanClient {
         redirect_uri: &str,
         state: Option<&str>,
     ) -> PodbeanResult<String> {
-        let mut url = Url::parse("https://api.podbean.com/v1/dialog/oauth")?;
+        self.get_authorization_url_with_scopes(redirect_uri, state, &Scopes::new())
+    }
+
+    /// Generates an authorization URL requesting specific scopes.
+    ///
+    /// Like [`get_authorization_url`](PodbeanClient::get_authorization_url),
+    /// but asks the user to grant `scopes`. The scopes granted are kept on
+    /// the token, and calls to endpoints whose documented scope it lacks
+    /// fail with [`PodbeanError::MissingScope`] without being sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `redirect_uri` - The URI to redirect to after authorization
+    /// * `state` - Optional state parameter for CSRF protection
+    /// * `scopes` - Scopes to request; the app's default scopes if empty
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use podbean::{PodbeanClient, Scope, Scopes};
+    /// let client = PodbeanClient::new("client_id", "client_secret").unwrap();
+    ///
+    /// let auth_url = client.get_authorization_url_with_scopes(
+    ///     "https://your-app.com/callback",
+    ///     Some("random_state_for_csrf_protection"),
+    ///     &Scopes::from([Scope::EpisodeRead, Scope::EpisodePublish]),
+    /// ).unwrap();
+    /// ```
+    pub fn get_authorization_url_with_scopes(
+        &self,
+        redirect_uri: &str,
+        state: Option<&str>,
+        scopes: &Scopes,
+    ) -> PodbeanResult<String> {
+        let mut url = Url::parse(&self.dialog_url)?;
 
         let _ = url
             .query_pairs_mut()
@@ -776,6 +1946,12 @@ impl PodbeanClient {
             .append_pair("client_id", &self.client_id)
             .append_pair("redirect_uri", redirect_uri);
 
+        if !scopes.is_empty() {
+            let _ = url
+                .query_pairs_mut()
+                .append_pair("scope", &scopes.to_string());
+        }
+
         if let Some(state_val) = state {
             let _ = url.query_pairs_mut().append_pair("state", state_val);
         }
@@ -783,3 +1959,280 @@ impl PodbeanClient {
         Ok(url.to_string())
     }
 }
+
+/// Response wrapping a single episode.
+#[derive(Debug, Deserialize)]
+struct EpisodeResponse {
+    episode: Episode,
+}
+
+/// Response from the multiple podcasts token endpoint.
+#[derive(Debug, Deserialize)]
+struct PodcastTokensResponse {
+    podcasts: Vec<PodcastTokenResponse>,
+}
+
+/// Token for a single podcast.
+#[derive(Debug, Deserialize)]
+struct PodcastTokenResponse {
+    podcast_id: PodcastId,
+    #[serde(flatten)]
+    token: TokenResponse,
+}
+
+/// Response wrapping a single podcast.
+#[derive(Debug, Deserialize)]
+struct PodcastResponse {
+    podcast: Podcast,
+}
+
+/// Parses the `Retry-After` header of a response as a number of seconds.
+fn retry_after(response: &HttpResponse) -> Option<u64> {
+    response
+        .headers
+        .get("Retry-After")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A request seen by the [`Recorder`].
+    #[derive(Debug, Clone)]
+    struct Recorded {
+        url: Url,
+        authorization: Option<String>,
+        form: Vec<(String, String)>,
+    }
+
+    impl Recorded {
+        /// Gets a form parameter of the request.
+        fn param(&self, key: &str) -> Option<&str> {
+            self.form
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        }
+    }
+
+    /// Answers token requests with a token and everything else with an
+    /// empty podcast and episode list, recording the requests.
+    ///
+    /// The next `rejections` API requests are answered with
+    /// `401 Unauthorized` instead.
+    #[derive(Debug, Default, Clone)]
+    struct Recorder {
+        requests: Arc<StdMutex<Vec<Recorded>>>,
+        rejections: Arc<AtomicUsize>,
+    }
+
+    impl Recorder {
+        /// Gets the recorded requests to the token endpoint.
+        fn token_requests(&self) -> Vec<Recorded> {
+            self.requests
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|request| request.url.path().ends_with("/oauth/token"))
+                .cloned()
+                .collect()
+        }
+    }
+
+    impl Transport for Recorder {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, PodbeanResult<HttpResponse>> {
+            let form = match &request.body {
+                RequestBody::Form(form) => form.clone(),
+                _ => Vec::new(),
+            };
+            let is_token_request = request.url.path().ends_with("/oauth/token");
+            let authorization = request
+                .headers
+                .get(AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            self.requests.lock().unwrap().push(Recorded {
+                url: request.url,
+                authorization,
+                form,
+            });
+
+            Box::pin(async move {
+                if is_token_request {
+                    // Keeps the token lock held long enough for concurrent
+                    // callers to queue up behind the renewal
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    let body =
+                        r#"{"access_token":"token","expires_in":3600,"refresh_token":"refresh"}"#;
+                    return Ok(HttpResponse::new(StatusCode::OK, body));
+                }
+
+                let rejected = self
+                    .rejections
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok();
+                if rejected {
+                    let body = r#"{"error":"invalid_token","error_description":"Expired"}"#;
+                    return Ok(HttpResponse::new(StatusCode::UNAUTHORIZED, body));
+                }
+
+                let body = r#"{"podcasts":[],"episodes":[],"count":0}"#;
+                Ok(HttpResponse::new(StatusCode::OK, body))
+            })
+        }
+    }
+
+    /// Builds a client sending its requests to `recorder`.
+    fn client(recorder: &Recorder) -> PodbeanClient {
+        PodbeanClient::builder("id", "secret")
+            .transport(recorder.clone())
+            .build()
+            .unwrap()
+    }
+
+    /// Builds a token obtained through the authorization code grant that
+    /// expired already.
+    fn expired_token() -> AuthToken {
+        let response = serde_json::from_str(
+            r#"{"access_token":"old","expires_in":0,"refresh_token":"refresh"}"#,
+        )
+        .unwrap();
+
+        AuthToken::new(response, Grant::AuthorizationCode)
+    }
+
+    #[tokio::test]
+    async fn sends_get_requests_without_params_without_a_query() {
+        let recorder = Recorder::default();
+        let client = client(&recorder);
+        client.authorize_client_credentials().await.unwrap();
+
+        let _ = client.list_podcasts(None, None).await.unwrap();
+        let _ = client.list_podcasts(None, Some(5)).await.unwrap();
+
+        let requests = recorder.requests.lock().unwrap();
+        assert_eq!(
+            requests[1].url.as_str(),
+            "https://api.podbean.com/v1/podcasts"
+        );
+        assert_eq!(requests[2].url.query(), Some("limit=5"));
+    }
+
+    #[tokio::test]
+    async fn requests_each_grant_with_its_own_params() {
+        let recorder = Recorder::default();
+        let client = client(&recorder);
+
+        client.authorize_client_credentials().await.unwrap();
+        let grant = client.token.lock().await.as_ref().map(AuthToken::grant);
+        assert_eq!(grant, Some(Grant::ClientCredentials));
+
+        client
+            .authorize("code", "http://localhost/callback")
+            .await
+            .unwrap();
+
+        let token_requests = recorder.token_requests();
+        let client_credentials = &token_requests[0];
+        assert_eq!(
+            client_credentials.form,
+            [("grant_type".to_string(), "client_credentials".to_string())]
+        );
+        assert_eq!(
+            client_credentials.authorization.as_deref(),
+            Some(format!("Basic {}", BASE64.encode("id:secret")).as_str())
+        );
+
+        let authorization_code = &token_requests[1];
+        assert_eq!(
+            authorization_code.param("grant_type"),
+            Some("authorization_code")
+        );
+        assert_eq!(authorization_code.param("code"), Some("code"));
+        assert_eq!(
+            authorization_code.param("redirect_uri"),
+            Some("http://localhost/callback")
+        );
+        assert_eq!(authorization_code.param("client_secret"), None);
+    }
+
+    #[tokio::test]
+    async fn refreshes_an_expired_token_once_for_concurrent_calls() {
+        let recorder = Recorder::default();
+        let client = client(&recorder);
+        *client.token.lock().await = Some(expired_token());
+
+        let (first, second, third, fourth) = tokio::join!(
+            client.list_episodes(None, None, None),
+            client.list_episodes(None, None, None),
+            client.list_episodes(None, None, None),
+            client.list_episodes(None, None, None),
+        );
+        for result in [first, second, third, fourth] {
+            assert_eq!(result.unwrap().count, 0);
+        }
+
+        let token_requests = recorder.token_requests();
+        assert_eq!(token_requests.len(), 1);
+        assert_eq!(token_requests[0].param("grant_type"), Some("refresh_token"));
+        assert_eq!(token_requests[0].param("refresh_token"), Some("refresh"));
+    }
+
+    #[tokio::test]
+    async fn refreshes_a_rejected_token_and_retries_only_once() {
+        let recorder = Recorder::default();
+        let client = client(&recorder);
+        client
+            .authorize("code", "http://localhost/callback")
+            .await
+            .unwrap();
+
+        recorder.rejections.store(1, Ordering::SeqCst);
+        assert_eq!(client.list_podcasts(None, None).await.unwrap().count, 0);
+        assert_eq!(recorder.token_requests().len(), 2);
+
+        recorder.rejections.store(2, Ordering::SeqCst);
+        let error = client.list_podcasts(None, None).await.unwrap_err();
+        assert!(matches!(
+            &error,
+            PodbeanError::ApiError(e) if e.status == StatusCode::UNAUTHORIZED
+        ));
+        assert!(error.is_auth());
+
+        let token_requests = recorder.token_requests();
+        assert_eq!(token_requests.len(), 3);
+        assert_eq!(token_requests[2].param("grant_type"), Some("refresh_token"));
+        // Two attempts for each call, and no further retries
+        assert_eq!(recorder.requests.lock().unwrap().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn percent_encodes_ids_in_paths() {
+        let recorder = Recorder::default();
+        let client = client(&recorder);
+        client.authorize_client_credentials().await.unwrap();
+
+        let _ = client.get_episode(&EpisodeId::from("a/b?c#d")).await;
+        let _ = client.get_podcast(&PodcastId::from("../me 2")).await;
+        let error = client
+            .get_podcast(&PodcastId::from(".."))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, PodbeanError::ValidationError { .. }));
+
+        let requests = recorder.requests.lock().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(
+            requests[1].url.as_str(),
+            "https://api.podbean.com/v1/episodes/a%2Fb%3Fc%23d"
+        );
+        assert_eq!(
+            requests[2].url.as_str(),
+            "https://api.podbean.com/v1/podcasts/..%2Fme%202"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Loopback redirect listener for the Podbean API client.
+//!
+//! This module defines [`LoopbackLogin`], which completes the authorization
+//! code flow for tools running on the user's own machine by receiving the
+//! browser redirect on a one-shot HTTP listener bound to `127.0.0.1`. It is
+//! only available with the `loopback` feature.
+
+use crate::{AuthToken, AuthorizationFlow, PodbeanClient, PodbeanResult, Scopes};
+use std::io;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use url::Url;
+
+/// Path of the redirect URI served by the listener.
+const CALLBACK_PATH: &str = "/callback";
+
+/// Largest request head read from the browser, in bytes.
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+
+/// Time allowed for a connection to send its request head.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Page shown in the browser once the app is authorized.
+const SUCCESS_PAGE: &str = "<!DOCTYPE html><html><head><title>Authorized</title></head>\
+    <body><h1>Authorization complete</h1>\
+    <p>You can close this window and return to the application.</p></body></html>";
+
+/// Page shown in the browser if authorization failed.
+const FAILURE_PAGE: &str = "<!DOCTYPE html><html><head><title>Authorization failed</title></head>\
+    <body><h1>Authorization failed</h1>\
+    <p>Return to the application for details.</p></body></html>";
+
+/// A login that receives the OAuth redirect on a local port.
+///
+/// [`start`](LoopbackLogin::start) binds a listener to an ephemeral port of
+/// `127.0.0.1` and prepares an [`AuthorizationFlow`] redirecting to it. Once
+/// the user has opened [`authorization_url`](LoopbackLogin::authorization_url)
+/// in a browser, [`finish`](LoopbackLogin::finish) waits for the redirect,
+/// shows a confirmation page and exchanges the code for a token.
+///
+/// The app must accept `http://127.0.0.1` redirect URIs. `finish` waits
+/// until the browser comes back, so wrap it in `tokio::time::timeout` to
+/// give up eventually.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use podbean::{LoopbackLogin, PodbeanClient, Scopes};
+/// # use tokio::runtime::Runtime;
+/// # let client = PodbeanClient::new("id", "secret").unwrap();
+/// # let rt = Runtime::new().unwrap();
+/// # rt.block_on(async {
+/// let login = LoopbackLogin::start(&client, Scopes::new()).await.unwrap();
+/// println!("Open this URL in your browser: {}", login.authorization_url());
+///
+/// let token = login.finish().await.unwrap();
+/// println!("Authorized until {:?}", token.expires_at());
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct LoopbackLogin<'a> {
+    client: &'a PodbeanClient,
+    listener: TcpListener,
+    flow: AuthorizationFlow,
+    authorization_url: String,
+}
+
+impl<'a> LoopbackLogin<'a> {
+    /// Binds the listener and prepares the authorization request.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to authorize
+    /// * `scopes` - Scopes to request; the app's default scopes if empty
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LoopbackLogin)` ready to send the user to the dialog
+    /// * `Err(PodbeanError)` if the listener could not be bound
+    pub async fn start(client: &'a PodbeanClient, scopes: Scopes) -> PodbeanResult<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let redirect_uri = format!("http://{}{}", listener.local_addr()?, CALLBACK_PATH);
+
+        let flow = AuthorizationFlow::new(&redirect_uri)?.scopes(scopes);
+        let authorization_url = flow.authorization_url(client)?;
+
+        Ok(Self {
+            client,
+            listener,
+            flow,
+            authorization_url,
+        })
+    }
+
+    /// Gets the redirect URI the listener answers, e.g.
+    /// `http://127.0.0.1:49152/callback`.
+    pub fn redirect_uri(&self) -> &str {
+        self.flow.redirect_uri()
+    }
+
+    /// Gets the URL of the authorization dialog to open in a browser.
+    pub fn authorization_url(&self) -> &str {
+        &self.authorization_url
+    }
+
+    /// Waits for the browser redirect and exchanges its code for a token,
+    /// which the client then uses.
+    ///
+    /// Requests for other paths, such as `/favicon.ico`, are answered with
+    /// `404 Not Found` while waiting, and callbacks whose `state` does not
+    /// match the login with `400 Bad Request`. Connections that send no
+    /// request within a few seconds are dropped.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(AuthToken)` obtained for the user
+    /// * `Err(PodbeanError)` if the redirect is invalid, the user denied
+    ///   access, or the code exchange failed
+    pub async fn finish(self) -> PodbeanResult<AuthToken> {
+        loop {
+            let (mut stream, _) = self.listener.accept().await?;
+
+            let target =
+                match tokio::time::timeout(READ_TIMEOUT, read_request_target(&mut stream)).await {
+                    Ok(Ok(Some(target))) => target,
+                    _ => continue,
+                };
+
+            if target.split('?').next() != Some(CALLBACK_PATH) {
+                let _ = respond(&mut stream, "404 Not Found", "").await;
+                continue;
+            }
+
+            let callback_url = format!("http://{}{}", self.listener.local_addr()?, target);
+            if !self.has_state(&callback_url) {
+                let _ = respond(&mut stream, "400 Bad Request", FAILURE_PAGE).await;
+                continue;
+            }
+
+            let result = self.flow.exchange(self.client, &callback_url).await;
+
+            let _ = match &result {
+                Ok(_) => respond(&mut stream, "200 OK", SUCCESS_PAGE).await,
+                Err(_) => respond(&mut stream, "400 Bad Request", FAILURE_PAGE).await,
+            };
+
+            return result;
+        }
+    }
+
+    /// Checks if a callback carries the `state` of this login, so that
+    /// forged requests do not end it.
+    fn has_state(&self, callback_url: &str) -> bool {
+        Url::parse(callback_url).is_ok_and(|url| {
+            url.query_pairs()
+                .any(|(name, value)| name == "state" && value == self.flow.state())
+        })
+    }
+}
+
+/// Reads the head of an HTTP request and returns the target of a `GET`
+/// request, e.g. `/callback?code=...`.
+async fn read_request_target(stream: &mut TcpStream) -> io::Result<Option<String>> {
+    let mut head = Vec::new();
+    let mut chunk = [0; 1024];
+
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        head.extend_from_slice(&chunk[..read]);
+    }
+
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => Ok(Some(target.to_string())),
+        _ => Ok(None),
+    }
+}
+
+/// Writes an HTML response and closes the connection.
+async fn respond(stream: &mut TcpStream, status: &str, page: &str) -> io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        page.len(),
+        page
+    );
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
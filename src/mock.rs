@@ -0,0 +1,1126 @@
+//! A local stand-in for the Podbean API.
+//!
+//! This module defines [`MockServer`], a stateful HTTP server that
+//! implements the OAuth endpoints, podcasts, episodes, media listing
+//! and presigned uploads well enough to exercise real request and response
+//! flows without network access. It is only available with the
+//! `mock-server` feature.
+
+use crate::flow::code_challenge;
+use crate::{MediaKey, PodbeanClientBuilder, Scope, Scopes};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response};
+use hyper_util::rt::TokioIo;
+use reqwest::{Method, StatusCode};
+use serde_json::{Map, Value, json};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use url::Url;
+
+/// Lifetime of the access tokens issued by the server, in seconds.
+const TOKEN_LIFETIME: u64 = 3600;
+
+/// Scopes the server requires, by method and path prefix.
+///
+/// Kept apart from the client's own checks so that tests exercise both
+/// sides. Updating and deleting episodes are treated like publishing.
+const REQUIRED_SCOPES: &[(&str, &str, Scope)] = &[
+    ("GET", "/podcasts", Scope::PodcastRead),
+    ("PUT", "/podcasts", Scope::PodcastUpdate),
+    ("GET", "/episodes", Scope::EpisodeRead),
+    ("POST", "/episodes", Scope::EpisodePublish),
+    ("PUT", "/episodes", Scope::EpisodePublish),
+    ("DELETE", "/episodes", Scope::EpisodePublish),
+    ("GET", "/files/uploadAuthorize", Scope::EpisodePublish),
+    ("GET", "/medias", Scope::EpisodeRead),
+];
+
+/// A fault to inject into a response of a [`MockServer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockFault {
+    /// Answer with `429 Too Many Requests`
+    RateLimited {
+        /// Value of the `Retry-After` header, in seconds
+        retry_after: Option<u64>,
+    },
+
+    /// Answer with `401 Unauthorized` and an `invalid_token` error
+    Unauthorized,
+
+    /// Answer with `500 Internal Server Error`
+    ServerError,
+
+    /// Wait before handling the request normally
+    Delay(Duration),
+}
+
+/// A request received by a [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// HTTP method
+    pub method: Method,
+
+    /// Path of the request URL, e.g. `/v1/episodes`
+    pub path: String,
+
+    /// Decoded query parameters
+    pub query: Vec<(String, String)>,
+
+    /// Value of the `Authorization` header, if any
+    pub authorization: Option<String>,
+
+    /// Raw request body
+    pub body: Bytes,
+}
+
+impl RecordedRequest {
+    /// Gets the decoded form parameters of the body.
+    pub fn form(&self) -> Vec<(String, String)> {
+        url::form_urlencoded::parse(&self.body)
+            .into_owned()
+            .collect()
+    }
+}
+
+/// A stateful stand-in for the Podbean API, listening on an ephemeral
+/// local port.
+///
+/// The server accepts the credentials [`MockServer::CLIENT_ID`] and
+/// [`MockServer::CLIENT_SECRET`], issues access and refresh tokens, and
+/// keeps the podcasts, episodes and uploaded files created through it.
+/// Faults such as rate limiting, rejected tokens and slow responses can be
+/// injected with [`fail_next`](MockServer::fail_next).
+///
+/// The server shuts down when dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::{MediaFormat, MockServer, NewEpisode, PodcastId};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let server = MockServer::start().await.unwrap();
+/// let client = server.client_builder().build().unwrap();
+/// client.authorize_client_credentials().await.unwrap();
+///
+/// let media_key = client
+///     .upload_media("episode.mp3".to_string(), vec![0; 1024], MediaFormat::Mp3)
+///     .await
+///     .unwrap();
+///
+/// let podcast_id = PodcastId::from(MockServer::PODCAST_ID);
+/// let episode = NewEpisode::new(&podcast_id, "Pilot", "The first episode").media_key(&media_key);
+/// let episode = client.publish_episode(&episode).await.unwrap();
+///
+/// assert_eq!(server.uploaded_file(&media_key).unwrap().len(), 1024);
+/// assert_eq!(client.get_episode(&episode.id).await.unwrap().title, "Pilot");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Client ID accepted by the server.
+    pub const CLIENT_ID: &'static str = "mock_client_id";
+
+    /// Client secret accepted by the server.
+    pub const CLIENT_SECRET: &'static str = "mock_client_secret";
+
+    /// ID of the podcast the server starts with.
+    pub const PODCAST_ID: &'static str = "mock_podcast";
+
+    /// Starts a server on an ephemeral port of `127.0.0.1`.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub async fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::new(addr)));
+
+        let task = tokio::spawn(serve(listener, Arc::clone(&state)));
+
+        Ok(Self { addr, state, task })
+    }
+
+    /// Gets the API base URL, e.g. `http://127.0.0.1:40123/v1`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    /// Creates a client builder pointed at this server with the accepted
+    /// credentials.
+    pub fn client_builder(&self) -> PodbeanClientBuilder {
+        PodbeanClientBuilder::new(Self::CLIENT_ID, Self::CLIENT_SECRET).base_url(&self.base_url())
+    }
+
+    /// Issues an authorization code, as if a user had approved the app in
+    /// the OAuth dialog.
+    pub fn authorization_code(&self) -> String {
+        self.lock().issue_code(None)
+    }
+
+    /// Approves an authorization request, as a user would in the OAuth
+    /// dialog, and returns the URL the browser is redirected to.
+    ///
+    /// The redirect carries a new code and the `state` of the request. If
+    /// the request has a PKCE `code_challenge`, the code can only be
+    /// exchanged with the matching code verifier.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `authorization_url` has no valid `redirect_uri`.
+    pub fn approve(&self, authorization_url: &str) -> String {
+        let request = Url::parse(authorization_url).expect("invalid authorization URL");
+        let code = self
+            .lock()
+            .issue_code(query_param(&request, "code_challenge"));
+
+        redirect(&request, &[("code", &code)])
+    }
+
+    /// Denies an authorization request, as a user would in the OAuth
+    /// dialog, and returns the URL the browser is redirected to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `authorization_url` has no valid `redirect_uri`.
+    pub fn deny(&self, authorization_url: &str) -> String {
+        let request = Url::parse(authorization_url).expect("invalid authorization URL");
+
+        redirect(
+            &request,
+            &[
+                ("error", "access_denied"),
+                ("error_description", "The user denied access"),
+            ],
+        )
+    }
+
+    /// Injects `fault` into the next response.
+    ///
+    /// Faults queue up and are applied to one request each, in order,
+    /// regardless of the endpoint.
+    pub fn fail_next(&self, fault: MockFault) {
+        self.lock().faults.push_back(fault);
+    }
+
+    /// Delays every response by `latency`.
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    /// Adds a podcast managed by the accepted credentials.
+    ///
+    /// Tokens for it can be obtained with
+    /// [`authorize_podcasts`](crate::PodbeanClient::authorize_podcasts).
+    pub fn add_podcast(&self, podcast_id: &str, title: &str) {
+        self.lock().add_podcast(podcast_id, title);
+    }
+
+    /// Sets the scopes granted to tokens issued from now on.
+    ///
+    /// Tokens start out with every scope except
+    /// [`Scope::PrivateMembers`]. Requests needing a scope the token lacks
+    /// are answered with `403 Forbidden`.
+    pub fn set_token_scopes(&self, scopes: Scopes) {
+        self.lock().token_scopes = scopes;
+    }
+
+    /// Revokes every access token issued so far.
+    ///
+    /// Refresh tokens stay valid, so clients can recover by refreshing.
+    pub fn revoke_access_tokens(&self) {
+        self.lock().access_tokens.clear();
+    }
+
+    /// Gets every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.lock().requests.clone()
+    }
+
+    /// Gets the contents of an uploaded file.
+    pub fn uploaded_file(&self, media_key: &MediaKey) -> Option<Bytes> {
+        self.lock().uploads.get(media_key.as_str())?.data.clone()
+    }
+
+    /// Gets the episodes stored on the server as JSON, newest first.
+    pub fn episodes(&self) -> Vec<Value> {
+        self.lock()
+            .episodes
+            .iter()
+            .cloned()
+            .map(Value::Object)
+            .collect()
+    }
+
+    /// Locks the server state.
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A file announced through `uploadAuthorize`.
+#[derive(Debug)]
+struct Upload {
+    file_name: String,
+    content_type: String,
+    size: u64,
+    data: Option<Bytes>,
+    created_at: u64,
+}
+
+/// An access token issued by the server.
+#[derive(Debug)]
+struct IssuedToken {
+    podcast_id: Option<String>,
+    scopes: Scopes,
+    expires_at: u64,
+}
+
+/// Everything the server remembers between requests.
+#[derive(Debug)]
+struct State {
+    addr: SocketAddr,
+    counter: u64,
+    latency: Duration,
+    faults: VecDeque<MockFault>,
+    requests: Vec<RecordedRequest>,
+    /// Unused authorization codes, with their PKCE code challenge
+    codes: HashMap<String, Option<String>>,
+    token_scopes: Scopes,
+    access_tokens: HashMap<String, IssuedToken>,
+    refresh_tokens: HashSet<String>,
+    podcasts: Vec<Map<String, Value>>,
+    episodes: Vec<Map<String, Value>>,
+    uploads: HashMap<String, Upload>,
+}
+
+impl State {
+    /// Creates the state of a server listening on `addr`, with one podcast.
+    fn new(addr: SocketAddr) -> Self {
+        let mut state = Self {
+            addr,
+            counter: 0,
+            latency: Duration::ZERO,
+            faults: VecDeque::new(),
+            requests: Vec::new(),
+            codes: HashMap::new(),
+            token_scopes: Scopes::from([
+                Scope::PodcastRead,
+                Scope::PodcastUpdate,
+                Scope::EpisodeRead,
+                Scope::EpisodePublish,
+            ]),
+            access_tokens: HashMap::new(),
+            refresh_tokens: HashSet::new(),
+            podcasts: Vec::new(),
+            episodes: Vec::new(),
+            uploads: HashMap::new(),
+        };
+
+        state.add_podcast(MockServer::PODCAST_ID, "Mock Podcast");
+        state
+    }
+
+    /// Adds a podcast managed by the accepted credentials.
+    fn add_podcast(&mut self, podcast_id: &str, title: &str) {
+        let podcast = json!({
+            "podcast_id": podcast_id,
+            "title": title,
+            "description": "A podcast served by the mock server",
+            "logo": format!("http://{}/files/logo.png", self.addr),
+            "url": format!("http://{}/podcasts/{}", self.addr, podcast_id),
+            "category": "Technology",
+            "subcategory": null,
+        });
+
+        self.podcasts.push(into_object(podcast));
+    }
+
+    /// Generates a new unique ID with the given prefix.
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.counter += 1;
+        format!("{}_{}", prefix, self.counter)
+    }
+
+    /// Issues an authorization code, bound to a PKCE code challenge if one
+    /// is given.
+    fn issue_code(&mut self, code_challenge: Option<String>) -> String {
+        let code = self.next_id("mock_code");
+        let _ = self.codes.insert(code.clone(), code_challenge);
+        code
+    }
+
+    /// Issues an access token, with a refresh token unless `app_only`.
+    fn issue_token(&mut self, app_only: bool) -> MockResponse {
+        let body = self.token_body(app_only, None);
+        MockResponse::json(StatusCode::OK, body)
+    }
+
+    /// Creates a token response, optionally scoped to a single podcast.
+    fn token_body(&mut self, app_only: bool, podcast_id: Option<&str>) -> Value {
+        let access_token = self.next_id("mock_access");
+        let issued = IssuedToken {
+            podcast_id: podcast_id.map(str::to_string),
+            scopes: self.token_scopes.clone(),
+            expires_at: unix_now() + TOKEN_LIFETIME,
+        };
+        let _ = self.access_tokens.insert(access_token.clone(), issued);
+
+        let mut body = json!({
+            "access_token": access_token,
+            "token_type": "Bearer",
+            "expires_in": TOKEN_LIFETIME,
+            "scope": self.token_scopes,
+        });
+
+        if !app_only {
+            let refresh_token = self.next_id("mock_refresh");
+            let _ = self.refresh_tokens.insert(refresh_token.clone());
+            body["refresh_token"] = json!(refresh_token);
+        }
+
+        body
+    }
+
+    /// Handles a request to the OAuth token endpoint.
+    fn token(&mut self, form: &HashMap<String, String>) -> MockResponse {
+        let param = |key: &str| form.get(key).map(String::as_str).unwrap_or_default();
+
+        match param("grant_type") {
+            "client_credentials" => self.issue_token(true),
+            "authorization_code"
+                if self.codes.remove(param("code")).is_some_and(|challenge| {
+                    challenge
+                        .is_none_or(|challenge| code_challenge(param("code_verifier")) == challenge)
+                }) =>
+            {
+                self.issue_token(false)
+            }
+            "refresh_token" if self.refresh_tokens.remove(param("refresh_token")) => {
+                self.issue_token(false)
+            }
+            "authorization_code" | "refresh_token" => MockResponse::error(
+                StatusCode::BAD_REQUEST,
+                "invalid_grant",
+                "The grant is invalid, expired or already used",
+            ),
+            _ => MockResponse::error(
+                StatusCode::BAD_REQUEST,
+                "unsupported_grant_type",
+                "The grant type is not supported",
+            ),
+        }
+    }
+
+    /// Handles a request to the multiple podcasts token endpoint.
+    fn podcast_tokens(&mut self, form: &HashMap<String, String>) -> MockResponse {
+        if param(form, "grant_type") != "client_credentials" {
+            return MockResponse::error(
+                StatusCode::BAD_REQUEST,
+                "unsupported_grant_type",
+                "The grant type is not supported",
+            );
+        }
+
+        let podcast_ids: Vec<String> = self
+            .podcasts
+            .iter()
+            .filter_map(|podcast| podcast["podcast_id"].as_str().map(str::to_string))
+            .collect();
+
+        let podcasts: Vec<Value> = podcast_ids
+            .iter()
+            .map(|podcast_id| {
+                let mut token = self.token_body(true, Some(podcast_id));
+                token["podcast_id"] = json!(podcast_id);
+                token
+            })
+            .collect();
+
+        MockResponse::json(StatusCode::OK, json!({ "podcasts": podcasts }))
+    }
+
+    /// Handles a request to the debug token endpoint.
+    fn debug_token(&self, params: &HashMap<String, String>) -> MockResponse {
+        let body = match self.access_tokens.get(param(params, "access_token")) {
+            Some(token) => json!({
+                "is_valid": true,
+                "podcast_id": token.podcast_id,
+                "scope": token.scopes,
+                "expires_at": token.expires_at,
+            }),
+            None => json!({ "is_valid": false }),
+        };
+
+        MockResponse::json(StatusCode::OK, body)
+    }
+
+    /// Handles a request to the token revocation endpoint.
+    fn revoke_token(&mut self, params: &HashMap<String, String>) -> MockResponse {
+        let token = param(params, "token");
+        if self.access_tokens.remove(token).is_none() && !self.refresh_tokens.remove(token) {
+            return MockResponse::error(
+                StatusCode::BAD_REQUEST,
+                "invalid_token",
+                "The token is invalid or already revoked",
+            );
+        }
+
+        MockResponse::json(StatusCode::OK, json!({ "msg": "success" }))
+    }
+
+    /// Finds the podcast an API request concerns, if any.
+    fn podcast_of(&self, path: &str, params: &HashMap<String, String>) -> Option<String> {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match segments.as_slice() {
+            ["podcasts", id] => Some(id.to_string()),
+            ["podcasts"] => params.get("id").cloned(),
+            ["episodes", id] => self.episode_podcast(id),
+            ["episodes"] => match params.get("podcast_id") {
+                Some(podcast_id) => Some(podcast_id.clone()),
+                None => self.episode_podcast(params.get("id")?),
+            },
+            _ => None,
+        }
+    }
+
+    /// Gets the podcast an existing episode belongs to.
+    fn episode_podcast(&self, episode_id: &str) -> Option<String> {
+        let episode = &self.episodes[find(&self.episodes, "id", episode_id)?];
+        episode["podcast_id"].as_str().map(str::to_string)
+    }
+
+    /// Handles an authenticated API request.
+    fn api(
+        &mut self,
+        method: &Method,
+        path: &str,
+        params: &HashMap<String, String>,
+    ) -> MockResponse {
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+            (&Method::GET, ["podcasts"]) => {
+                let podcasts = page(&self.podcasts, params);
+                MockResponse::json(
+                    StatusCode::OK,
+                    json!({ "count": self.podcasts.len(), "podcasts": podcasts }),
+                )
+            }
+            (&Method::GET, ["podcasts", id]) => match find(&self.podcasts, "podcast_id", id) {
+                Some(podcast) => {
+                    MockResponse::json(StatusCode::OK, json!({ "podcast": self.podcasts[podcast] }))
+                }
+                None => not_found("podcast"),
+            },
+            (&Method::PUT, ["podcasts"]) => self.update_podcast(params),
+            (&Method::GET, ["episodes"]) => {
+                let episodes: Vec<Map<String, Value>> = self
+                    .episodes
+                    .iter()
+                    .filter(|e| {
+                        params
+                            .get("podcast_id")
+                            .is_none_or(|id| e["podcast_id"] == **id)
+                    })
+                    .cloned()
+                    .collect();
+
+                MockResponse::json(
+                    StatusCode::OK,
+                    json!({ "count": episodes.len(), "episodes": page(&episodes, params) }),
+                )
+            }
+            (&Method::GET, ["episodes", id]) => match find(&self.episodes, "id", id) {
+                Some(episode) => {
+                    MockResponse::json(StatusCode::OK, json!({ "episode": self.episodes[episode] }))
+                }
+                None => not_found("episode"),
+            },
+            (&Method::POST, ["episodes"]) => self.publish_episode(params),
+            (&Method::PUT, ["episodes"]) => self.update_episode(params),
+            (&Method::DELETE, ["episodes"]) => {
+                match find(&self.episodes, "id", param(params, "id")) {
+                    Some(episode) => {
+                        let _ = self.episodes.remove(episode);
+                        MockResponse::json(StatusCode::OK, json!({ "msg": "success" }))
+                    }
+                    None => not_found("episode"),
+                }
+            }
+            (&Method::GET, ["medias"]) => self.list_media(params),
+            (&Method::GET, ["files", "uploadAuthorize"]) => self.authorize_upload(params),
+            _ => not_found("endpoint"),
+        }
+    }
+
+    /// Creates an episode from form parameters.
+    fn publish_episode(&mut self, params: &HashMap<String, String>) -> MockResponse {
+        for field in ["podcast_id", "title", "content"] {
+            if param(params, field).is_empty() {
+                return invalid_param(field, "is required");
+            }
+        }
+
+        if find(&self.podcasts, "podcast_id", param(params, "podcast_id")).is_none() {
+            return not_found("podcast");
+        }
+
+        let id = self.next_id("mock_episode");
+        let mut episode = into_object(json!({
+            "id": id,
+            "podcast_id": param(params, "podcast_id"),
+            "title": "",
+            "content": "",
+            "media_url": "",
+            "player_url": format!("http://{}/player/{}", self.addr, id),
+            "permalink_url": format!("http://{}/e/{}", self.addr, id),
+            "publish_time": "",
+            "duration": null,
+            "status": "draft",
+            "type": "public",
+            "transcripts_url": null,
+        }));
+
+        if let Err(response) = self.apply_episode_params(&mut episode, params) {
+            return response;
+        }
+
+        self.episodes.insert(0, episode.clone());
+
+        MockResponse::json(StatusCode::OK, json!({ "episode": episode }))
+    }
+
+    /// Applies a partial update to an episode.
+    fn update_episode(&mut self, params: &HashMap<String, String>) -> MockResponse {
+        let Some(index) = find(&self.episodes, "id", param(params, "id")) else {
+            return not_found("episode");
+        };
+
+        let mut episode = self.episodes[index].clone();
+        if let Err(response) = self.apply_episode_params(&mut episode, params) {
+            return response;
+        }
+        self.episodes[index] = episode.clone();
+
+        MockResponse::json(StatusCode::OK, json!({ "episode": episode }))
+    }
+
+    /// Copies episode fields from request parameters into `episode`.
+    fn apply_episode_params(
+        &self,
+        episode: &mut Map<String, Value>,
+        params: &HashMap<String, String>,
+    ) -> Result<(), MockResponse> {
+        for field in ["title", "content", "status", "type"] {
+            if let Some(value) = params.get(field) {
+                let _ = episode.insert(field.to_string(), json!(value));
+            }
+        }
+
+        if let Some(timestamp) = params.get("publish_timestamp") {
+            let Ok(timestamp) = timestamp.parse::<i64>() else {
+                return Err(invalid_param(
+                    "publish_timestamp",
+                    "must be a Unix timestamp",
+                ));
+            };
+            let _ = episode.insert("publish_time".to_string(), json!(timestamp));
+        }
+
+        // Like Podbean, drafts have an empty publish time until published
+        if episode["publish_time"] == "" && episode["status"] == "publish" {
+            let _ = episode.insert("publish_time".to_string(), json!(unix_now()));
+        }
+
+        match params.get("media_key").map(String::as_str) {
+            Some("") => {
+                let _ = episode.insert("media_url".to_string(), json!(""));
+            }
+            Some(key) => match self.uploads.get(key) {
+                Some(upload) if upload.data.is_some() => {
+                    let url = format!("http://{}/files/{}/{}", self.addr, key, upload.file_name);
+                    let _ = episode.insert("media_url".to_string(), json!(url));
+                }
+                _ => return Err(invalid_param("media_key", "is not an uploaded file")),
+            },
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Applies a partial update to a podcast.
+    fn update_podcast(&mut self, params: &HashMap<String, String>) -> MockResponse {
+        let Some(index) = find(&self.podcasts, "podcast_id", param(params, "id")) else {
+            return not_found("podcast");
+        };
+
+        let podcast = &mut self.podcasts[index];
+        for field in ["title", "description", "category", "subcategory"] {
+            match params.get(field).map(String::as_str) {
+                Some("") => {
+                    let _ = podcast.insert(field.to_string(), Value::Null);
+                }
+                Some(value) => {
+                    let _ = podcast.insert(field.to_string(), json!(value));
+                }
+                None => {}
+            }
+        }
+
+        if let Some(key) = params.get("logo_key") {
+            let url = format!("http://{}/files/{}", self.addr, key);
+            let _ = podcast.insert("logo".to_string(), json!(url));
+        }
+
+        MockResponse::json(StatusCode::OK, json!({ "podcast": podcast }))
+    }
+
+    /// Lists the uploaded media files, newest first.
+    fn list_media(&self, params: &HashMap<String, String>) -> MockResponse {
+        let mut uploads: Vec<(&String, &Upload)> = self
+            .uploads
+            .iter()
+            .filter(|(_, upload)| upload.data.is_some())
+            .collect();
+        uploads.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at).then(b.0.cmp(a.0)));
+
+        let media: Vec<Map<String, Value>> = uploads
+            .into_iter()
+            .map(|(key, upload)| {
+                into_object(json!({
+                    "media_key": key,
+                    "title": upload.file_name,
+                    "content": "",
+                    "status": "finished",
+                    "media_url": format!("http://{}/files/{}/{}", self.addr, key, upload.file_name),
+                    "logo_url": null,
+                    "player_url": null,
+                    "publish_time": null,
+                    "created_at": upload.created_at,
+                    "duration": null,
+                }))
+            })
+            .collect();
+
+        MockResponse::json(
+            StatusCode::OK,
+            json!({ "count": media.len(), "media": page(&media, params) }),
+        )
+    }
+
+    /// Announces an upload and returns its presigned URL.
+    fn authorize_upload(&mut self, params: &HashMap<String, String>) -> MockResponse {
+        let file_name = param(params, "filename");
+        if file_name.is_empty() {
+            return invalid_param("filename", "is required");
+        }
+
+        let Ok(size) = param(params, "filesize").parse::<u64>() else {
+            return invalid_param("filesize", "must be a number of bytes");
+        };
+
+        let file_key = self.next_id("mock_file");
+        let upload = Upload {
+            file_name: file_name.to_string(),
+            content_type: param(params, "content_type").to_string(),
+            size,
+            data: None,
+            created_at: unix_now(),
+        };
+        let _ = self.uploads.insert(file_key.clone(), upload);
+
+        MockResponse::json(
+            StatusCode::OK,
+            json!({
+                "presigned_url": format!("http://{}/upload/{}", self.addr, file_key),
+                "expire_at": unix_now() + 600,
+                "file_key": file_key,
+            }),
+        )
+    }
+
+    /// Stores a file sent to its presigned URL.
+    fn receive_upload(&mut self, file_key: &str, content_type: &str, body: Bytes) -> MockResponse {
+        let Some(upload) = self.uploads.get_mut(file_key) else {
+            return MockResponse::text(StatusCode::FORBIDDEN, "Unknown upload");
+        };
+
+        if upload.size != body.len() as u64 {
+            return MockResponse::text(StatusCode::BAD_REQUEST, "Content length mismatch");
+        }
+
+        if !upload.content_type.is_empty() && upload.content_type != content_type {
+            return MockResponse::text(StatusCode::FORBIDDEN, "Content type mismatch");
+        }
+
+        upload.data = Some(body);
+
+        MockResponse::text(StatusCode::OK, "")
+    }
+}
+
+/// A response produced by the server.
+#[derive(Debug)]
+struct MockResponse {
+    status: StatusCode,
+    headers: Vec<(&'static str, String)>,
+    body: Bytes,
+}
+
+impl MockResponse {
+    /// Creates a JSON response.
+    fn json(status: StatusCode, body: Value) -> Self {
+        Self {
+            status,
+            headers: vec![("Content-Type", "application/json".to_string())],
+            body: Bytes::from(body.to_string()),
+        }
+    }
+
+    /// Creates a plain text response.
+    fn text(status: StatusCode, body: &'static str) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    /// Creates an error response in Podbean's format.
+    fn error(status: StatusCode, error: &str, description: &str) -> Self {
+        Self::json(
+            status,
+            json!({ "error": error, "error_description": description }),
+        )
+    }
+
+    /// Creates the response for an injected fault.
+    fn fault(fault: &MockFault) -> Option<Self> {
+        match fault {
+            MockFault::RateLimited { retry_after } => {
+                let mut response = Self::error(
+                    StatusCode::TOO_MANY_REQUESTS,
+                    "rate_limited",
+                    "Too many requests",
+                );
+                if let Some(seconds) = retry_after {
+                    response.headers.push(("Retry-After", seconds.to_string()));
+                }
+                Some(response)
+            }
+            MockFault::Unauthorized => Some(Self::error(
+                StatusCode::UNAUTHORIZED,
+                "invalid_token",
+                "The access token is invalid",
+            )),
+            MockFault::ServerError => Some(Self::error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "server_error",
+                "Internal server error",
+            )),
+            MockFault::Delay(_) => None,
+        }
+    }
+}
+
+/// Accepts connections until the server is dropped.
+async fn serve(listener: TcpListener, state: Arc<Mutex<State>>) {
+    loop {
+        let Ok((stream, _)) = listener.accept().await else {
+            // Errors such as running out of file descriptors persist for a
+            // while, so wait instead of spinning on them.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            continue;
+        };
+
+        let state = Arc::clone(&state);
+        let service = service_fn(move |request| handle(Arc::clone(&state), request));
+
+        drop(tokio::spawn(async move {
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        }));
+    }
+}
+
+/// Handles a single request.
+async fn handle(
+    state: Arc<Mutex<State>>,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let (parts, body) = request.into_parts();
+    let body = body
+        .collect()
+        .await
+        .map(|b| b.to_bytes())
+        .unwrap_or_default();
+    let header = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+
+    let recorded = RecordedRequest {
+        method: parts.method.clone(),
+        path: parts.uri.path().to_string(),
+        query: url::form_urlencoded::parse(parts.uri.query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect(),
+        authorization: header("Authorization"),
+        body,
+    };
+
+    let (latency, fault) = {
+        let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.requests.push(recorded.clone());
+        (state.latency, state.faults.pop_front())
+    };
+
+    let delay = match &fault {
+        Some(MockFault::Delay(delay)) => latency + *delay,
+        _ => latency,
+    };
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+
+    let response = match fault.as_ref().and_then(MockResponse::fault) {
+        Some(response) => response,
+        None => {
+            let mut state = state.lock().unwrap_or_else(PoisonError::into_inner);
+            route(
+                &mut state,
+                &recorded,
+                header("Content-Type").unwrap_or_default(),
+            )
+        }
+    };
+
+    let mut builder = Response::builder().status(response.status);
+    for (name, value) in response.headers {
+        builder = builder.header(name, value);
+    }
+
+    Ok(builder
+        .body(Full::new(response.body))
+        .unwrap_or_else(|_| Response::new(Full::default())))
+}
+
+/// Dispatches a request to the matching endpoint.
+fn route(state: &mut State, request: &RecordedRequest, content_type: String) -> MockResponse {
+    if let Some(file_key) = request.path.strip_prefix("/upload/") {
+        return if request.method == Method::PUT {
+            state.receive_upload(file_key, &content_type, request.body.clone())
+        } else {
+            MockResponse::text(StatusCode::METHOD_NOT_ALLOWED, "")
+        };
+    }
+
+    let Some(path) = request.path.strip_prefix("/v1") else {
+        return not_found("endpoint");
+    };
+
+    let mut params: HashMap<String, String> = request.query.iter().cloned().collect();
+    params.extend(request.form());
+
+    if path.starts_with("/oauth/") {
+        if let Err(response) = check_client(request) {
+            return response;
+        }
+
+        return match (&request.method, path) {
+            (&Method::POST, "/oauth/token") => state.token(&params),
+            (&Method::POST, "/oauth/multiplePodcastsToken") => state.podcast_tokens(&params),
+            (&Method::GET, "/oauth/debugToken") => state.debug_token(&params),
+            (&Method::POST, "/oauth/revokeToken") => state.revoke_token(&params),
+            _ => not_found("endpoint"),
+        };
+    }
+
+    let token = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| state.access_tokens.get(token));
+
+    let Some(token) = token else {
+        return MockResponse::error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_token",
+            "The access token is missing or invalid",
+        );
+    };
+
+    if let Some(scope) = required_scope(&request.method, path)
+        && !token.scopes.contains(scope)
+    {
+        return MockResponse::error(
+            StatusCode::FORBIDDEN,
+            "insufficient_scope",
+            &format!("The access token lacks the {} scope", scope),
+        );
+    }
+
+    if let Some(token_podcast) = &token.podcast_id
+        && state
+            .podcast_of(path, &params)
+            .is_some_and(|podcast_id| podcast_id != *token_podcast)
+    {
+        return MockResponse::error(
+            StatusCode::FORBIDDEN,
+            "insufficient_scope",
+            "The access token does not grant access to this podcast",
+        );
+    }
+
+    state.api(&request.method, path, &params)
+}
+
+/// Gets a query parameter of `url`.
+fn query_param(url: &Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Builds the redirect answering an authorization request, carrying
+/// `params` and the `state` of the request.
+fn redirect(request: &Url, params: &[(&str, &str)]) -> String {
+    let redirect_uri = query_param(request, "redirect_uri").unwrap_or_default();
+    let mut url = Url::parse(&redirect_uri).expect("invalid redirect_uri");
+
+    {
+        let mut query = url.query_pairs_mut();
+        let _ = query.extend_pairs(params);
+
+        if let Some(state) = query_param(request, "state") {
+            let _ = query.append_pair("state", &state);
+        }
+    }
+
+    url.to_string()
+}
+
+/// Checks the client credentials sent to an OAuth endpoint.
+///
+/// Like Podbean, the server only accepts them through HTTP Basic
+/// authentication.
+fn check_client(request: &RecordedRequest) -> Result<(), MockResponse> {
+    let credentials = request
+        .authorization
+        .as_deref()
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| BASE64.decode(encoded).ok())
+        .and_then(|decoded| String::from_utf8(decoded).ok());
+    let expected = format!("{}:{}", MockServer::CLIENT_ID, MockServer::CLIENT_SECRET);
+
+    if credentials.as_deref() != Some(expected.as_str()) {
+        return Err(MockResponse::error(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            "Client authentication failed",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Gets the scope the server requires for a request, if any.
+fn required_scope(method: &Method, path: &str) -> Option<&'static Scope> {
+    REQUIRED_SCOPES
+        .iter()
+        .find(|(scope_method, prefix, _)| {
+            method == *scope_method
+                && path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+        .map(|(_, _, scope)| scope)
+}
+
+/// Gets a request parameter, or an empty string if it is missing.
+fn param<'a>(params: &'a HashMap<String, String>, key: &str) -> &'a str {
+    params.get(key).map(String::as_str).unwrap_or_default()
+}
+
+/// Finds the position of the object whose `key` equals `id`.
+fn find(objects: &[Map<String, Value>], key: &str, id: &str) -> Option<usize> {
+    objects.iter().position(|object| object[key] == *id)
+}
+
+/// Applies the `offset` and `limit` parameters to a list.
+fn page(
+    objects: &[Map<String, Value>],
+    params: &HashMap<String, String>,
+) -> Vec<Map<String, Value>> {
+    let offset = param(params, "offset").parse().unwrap_or(0);
+    let limit = param(params, "limit").parse().unwrap_or(10);
+
+    objects.iter().skip(offset).take(limit).cloned().collect()
+}
+
+/// Creates the response for a missing resource.
+fn not_found(resource: &str) -> MockResponse {
+    MockResponse::error(
+        StatusCode::NOT_FOUND,
+        "not_found",
+        &format!("The {} does not exist", resource),
+    )
+}
+
+/// Creates the response for an invalid request parameter.
+fn invalid_param(field: &str, message: &str) -> MockResponse {
+    MockResponse::json(
+        StatusCode::BAD_REQUEST,
+        json!({
+            "error": "input_params_invalid",
+            "error_description": format!("{} {}", field, message),
+            "field": field,
+        }),
+    )
+}
+
+/// Unwraps a JSON object literal.
+fn into_object(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(object) => object,
+        _ => Map::new(),
+    }
+}
+
+/// Returns the current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
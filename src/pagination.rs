@@ -0,0 +1,145 @@
+//! Auto-pagination for list endpoints.
+//!
+//! This module turns the offset-based list endpoints of the Podbean API
+//! into streams of items that fetch pages lazily.
+
+use crate::PodbeanResult;
+use futures_core::Stream;
+use futures_util::stream;
+use std::future::Future;
+
+/// Pagination state carried between pages.
+struct Pages<T, F> {
+    fetch: F,
+    page_size: u32,
+    offset: u32,
+    page: std::vec::IntoIter<T>,
+    done: bool,
+}
+
+/// Streams every item of a paginated list.
+///
+/// `fetch` is called with an offset and a limit and returns the total item
+/// count together with one page of items. Pages are requested only once
+/// the previous one has been consumed, and the stream ends after `count`
+/// items or at the first empty page. An error ends the stream after it has
+/// been yielded.
+pub(crate) fn paginate<'a, T, F, Fut>(
+    page_size: u32,
+    fetch: F,
+) -> impl Stream<Item = PodbeanResult<T>> + 'a
+where
+    T: 'a,
+    F: FnMut(u32, u32) -> Fut + 'a,
+    Fut: Future<Output = PodbeanResult<(u32, Vec<T>)>> + 'a,
+{
+    let pages = Pages {
+        fetch,
+        page_size: page_size.max(1),
+        offset: 0,
+        page: Vec::new().into_iter(),
+        done: false,
+    };
+
+    stream::try_unfold(pages, |mut pages| async move {
+        loop {
+            if let Some(item) = pages.page.next() {
+                return Ok(Some((item, pages)));
+            }
+
+            if pages.done {
+                return Ok(None);
+            }
+
+            let (count, items) = (pages.fetch)(pages.offset, pages.page_size).await?;
+            pages.offset = pages.offset.saturating_add(items.len() as u32);
+            pages.done = items.is_empty() || pages.offset >= count;
+            pages.page = items.into_iter();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PodbeanError;
+    use futures_util::{StreamExt, TryStreamExt};
+    use std::cell::RefCell;
+
+    /// Paginates over `items`, reporting `count` and recording each request.
+    async fn collect(
+        page_size: u32,
+        count: u32,
+        items: &[u32],
+        requests: &RefCell<Vec<(u32, u32)>>,
+    ) -> PodbeanResult<Vec<u32>> {
+        paginate(page_size, |offset, limit| {
+            requests.borrow_mut().push((offset, limit));
+            let page = items
+                .iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .copied()
+                .collect();
+            async move { Ok((count, page)) }
+        })
+        .try_collect()
+        .await
+    }
+
+    #[tokio::test]
+    async fn ends_on_a_short_last_page() {
+        let requests = RefCell::new(Vec::new());
+
+        let items = collect(2, 5, &[1, 2, 3, 4, 5], &requests).await.unwrap();
+
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+        assert_eq!(*requests.borrow(), [(0, 2), (2, 2), (4, 2)]);
+    }
+
+    #[tokio::test]
+    async fn ends_on_an_empty_page_before_the_count() {
+        let requests = RefCell::new(Vec::new());
+
+        // The count is stale and promises more items than there are
+        let items = collect(2, 10, &[1, 2, 3], &requests).await.unwrap();
+
+        assert_eq!(items, [1, 2, 3]);
+        assert_eq!(*requests.borrow(), [(0, 2), (2, 2), (3, 2)]);
+    }
+
+    #[tokio::test]
+    async fn ends_on_an_empty_first_page() {
+        let requests = RefCell::new(Vec::new());
+
+        let items = collect(2, 0, &[], &requests).await.unwrap();
+
+        assert!(items.is_empty());
+        assert_eq!(*requests.borrow(), [(0, 2)]);
+    }
+
+    #[tokio::test]
+    async fn treats_a_zero_page_size_as_one() {
+        let requests = RefCell::new(Vec::new());
+
+        let items = collect(0, 2, &[1, 2], &requests).await.unwrap();
+
+        assert_eq!(items, [1, 2]);
+        assert_eq!(*requests.borrow(), [(0, 1), (1, 1)]);
+    }
+
+    #[tokio::test]
+    async fn stops_after_an_error() {
+        let mut calls = 0;
+        let results: Vec<PodbeanResult<u32>> = paginate(2, |_, _| {
+            calls += 1;
+            async { Err(PodbeanError::OtherError("down".to_string())) }
+        })
+        .collect()
+        .await;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+        assert_eq!(calls, 1);
+    }
+}
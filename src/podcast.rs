@@ -0,0 +1,123 @@
+//! Request types for editing podcasts.
+//!
+//! This module defines [`PodcastUpdate`], a partial update of the
+//! podcast-level settings of a show.
+
+use crate::episode::FieldUpdate;
+use crate::id::{MediaKey, PodcastId};
+use crate::{PodbeanError, PodbeanResult};
+use std::collections::HashMap;
+
+/// A partial update of a podcast for
+/// [`PodbeanClient::update_podcast`](crate::PodbeanClient::update_podcast).
+///
+/// Fields that are not set are left unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::{MediaKey, PodcastUpdate};
+///
+/// let update = PodcastUpdate::new()
+///     .description("A weekly show about Rust")
+///     .logo_key(&MediaKey::from("logo_file_key"))
+///     .category("Technology");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PodcastUpdate {
+    title: Option<String>,
+    description: Option<String>,
+    logo_key: Option<MediaKey>,
+    category: Option<String>,
+    subcategory: FieldUpdate<String>,
+}
+
+impl PodcastUpdate {
+    /// Creates an update that leaves every field unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a new title.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets a new description.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Replaces the podcast artwork with a newly uploaded file.
+    pub fn logo_key(mut self, logo_key: &MediaKey) -> Self {
+        self.logo_key = Some(logo_key.clone());
+        self
+    }
+
+    /// Sets a new primary category.
+    pub fn category(mut self, category: &str) -> Self {
+        self.category = Some(category.to_string());
+        self
+    }
+
+    /// Sets a new secondary category.
+    pub fn subcategory(mut self, subcategory: &str) -> Self {
+        self.subcategory = FieldUpdate::Set(subcategory.to_string());
+        self
+    }
+
+    /// Removes the secondary category.
+    pub fn clear_subcategory(mut self) -> Self {
+        self.subcategory = FieldUpdate::Clear;
+        self
+    }
+
+    /// Checks that the update can be applied.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if every changed field is valid
+    /// * `Err(PodbeanError::ValidationError)` naming the first invalid field
+    pub fn validate(&self) -> PodbeanResult<()> {
+        let required = [
+            ("title", self.title.as_deref()),
+            ("logo_key", self.logo_key.as_ref().map(MediaKey::as_str)),
+            ("category", self.category.as_deref()),
+        ];
+
+        for (field, value) in required {
+            if let Some(value) = value
+                && value.trim().is_empty()
+            {
+                return Err(PodbeanError::validation(field, "must not be empty"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts the update into request parameters for `podcast_id`.
+    pub(crate) fn to_params(&self, podcast_id: &PodcastId) -> HashMap<String, String> {
+        let mut params = HashMap::new();
+        let _ = params.insert("id".to_string(), podcast_id.to_string());
+
+        let fields = [
+            ("title", self.title.as_deref()),
+            ("description", self.description.as_deref()),
+            ("logo_key", self.logo_key.as_ref().map(MediaKey::as_str)),
+            ("category", self.category.as_deref()),
+        ];
+
+        for (key, value) in fields {
+            if let Some(value) = value {
+                let _ = params.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        self.subcategory.insert_param(&mut params, "subcategory");
+
+        params
+    }
+}
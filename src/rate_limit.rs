@@ -0,0 +1,258 @@
+//! Client-side rate limiting for the Podbean API client.
+//!
+//! This module implements a token-bucket limiter shared by every clone of
+//! a [`PodbeanClient`](crate::PodbeanClient). When the server answers with
+//! `429 Too Many Requests` the limiter pauses for the advertised
+//! `Retry-After` and halves its rate, then recovers gradually as requests
+//! succeed again.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Wait applied after a `429` response that carries no `Retry-After` header.
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// Longest pause honoured from a `Retry-After` header.
+pub(crate) const MAX_RETRY_AFTER: Duration = Duration::from_secs(60 * 60);
+
+/// Lowest rate the limiter backs off to, as a fraction of the configured rate.
+const MIN_RATE_DIVISOR: f64 = 16.0;
+
+/// Configuration for the client-side rate limiter.
+///
+/// A limit allows bursts of up to `requests` calls and refills at a steady
+/// rate of `requests` per `per`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use podbean::{PodbeanClient, RateLimit};
+///
+/// let client = PodbeanClient::builder("your_client_id", "your_client_secret")
+///     .rate_limit(RateLimit::per_minute(120))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    requests: u32,
+    per: Duration,
+}
+
+impl RateLimit {
+    /// Allows `requests` calls per `per` interval.
+    ///
+    /// A zero request count or interval is treated as one.
+    pub fn new(requests: u32, per: Duration) -> Self {
+        Self {
+            requests: requests.max(1),
+            per: per.max(Duration::from_millis(1)),
+        }
+    }
+
+    /// Allows `requests` calls per second.
+    pub fn per_second(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(1))
+    }
+
+    /// Allows `requests` calls per minute.
+    pub fn per_minute(requests: u32) -> Self {
+        Self::new(requests, Duration::from_secs(60))
+    }
+
+    /// Refill rate in requests per second.
+    fn rate(&self) -> f64 {
+        f64::from(self.requests) / self.per.as_secs_f64()
+    }
+}
+
+impl Default for RateLimit {
+    /// Ten requests per second.
+    fn default() -> Self {
+        Self::per_second(10)
+    }
+}
+
+/// Token-bucket state shared between clones of the limiter.
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    configured_rate: f64,
+    rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    /// Adds the tokens accumulated since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Takes a token, or returns how long to wait before trying again.
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.paused_until {
+            if until > now {
+                return Err(until - now);
+            }
+            self.paused_until = None;
+            self.last_refill = now;
+        }
+
+        self.refill(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+/// A token-bucket rate limiter shared by every clone of a client.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter starting with a full bucket.
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        let rate = limit.rate();
+        let capacity = f64::from(limit.requests);
+
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                capacity,
+                configured_rate: rate,
+                rate,
+                tokens: capacity,
+                last_refill: Instant::now(),
+                paused_until: None,
+            })),
+        }
+    }
+
+    /// Waits until a request may be sent.
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let wait = match self.lock().try_acquire(Instant::now()) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Records a response the server did not reject for rate limiting.
+    ///
+    /// The rate recovers additively towards the configured rate.
+    pub(crate) fn record_success(&self) {
+        let mut bucket = self.lock();
+        let step = bucket.configured_rate / MIN_RATE_DIVISOR;
+        bucket.rate = (bucket.rate + step).min(bucket.configured_rate);
+    }
+
+    /// Records a `429 Too Many Requests` response.
+    ///
+    /// Pauses all requests for `retry_after` seconds, or one second if the
+    /// server did not say, and halves the rate. Pauses are capped at
+    /// [`MAX_RETRY_AFTER`].
+    pub(crate) fn record_rate_limited(&self, retry_after: Option<u64>) {
+        let now = Instant::now();
+        let wait = retry_after
+            .map_or(DEFAULT_RETRY_AFTER, Duration::from_secs)
+            .min(MAX_RETRY_AFTER);
+        let until = now.checked_add(wait).unwrap_or(now);
+        let mut bucket = self.lock();
+
+        bucket.refill(now);
+        bucket.tokens = 0.0;
+        bucket.rate = (bucket.rate / 2.0).max(bucket.configured_rate / MIN_RATE_DIVISOR);
+        bucket.paused_until = Some(bucket.paused_until.map_or(until, |u| u.max(until)));
+    }
+
+    /// Locks the bucket state.
+    fn lock(&self) -> MutexGuard<'_, Bucket> {
+        self.bucket.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Acquires `count` tokens, returning how long it took.
+    async fn acquire(limiter: &RateLimiter, count: u32) -> Duration {
+        let start = Instant::now();
+        for _ in 0..count {
+            limiter.acquire().await;
+        }
+        start.elapsed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn allows_a_burst_up_to_the_limit() {
+        let limiter = RateLimiter::new(RateLimit::per_second(4));
+
+        assert_eq!(acquire(&limiter, 4).await, Duration::ZERO);
+        assert_eq!(acquire(&limiter, 1).await, Duration::from_millis(250));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refills_at_the_configured_rate() {
+        let limiter = RateLimiter::new(RateLimit::per_second(4));
+        let _ = acquire(&limiter, 4).await;
+
+        tokio::time::advance(Duration::from_millis(500)).await;
+        assert_eq!(acquire(&limiter, 2).await, Duration::ZERO);
+
+        // The bucket never holds more than one burst
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(acquire(&limiter, 4).await, Duration::ZERO);
+        assert!(acquire(&limiter, 1).await > Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pauses_and_slows_down_when_rate_limited() {
+        let limiter = RateLimiter::new(RateLimit::per_second(4));
+
+        // The bucket is emptied and refills at half the rate after the pause
+        limiter.record_rate_limited(Some(5));
+        assert_eq!(acquire(&limiter, 1).await, Duration::from_millis(5500));
+        assert_eq!(acquire(&limiter, 1).await, Duration::from_millis(500));
+
+        for _ in 0..MIN_RATE_DIVISOR as u32 {
+            limiter.record_success();
+        }
+        assert_eq!(acquire(&limiter, 1).await, Duration::from_millis(250));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_a_second_without_retry_after() {
+        let limiter = RateLimiter::new(RateLimit::per_second(4));
+
+        limiter.record_rate_limited(None);
+        assert_eq!(
+            acquire(&limiter, 1).await,
+            DEFAULT_RETRY_AFTER + Duration::from_millis(500)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn caps_huge_retry_after_values() {
+        let limiter = RateLimiter::new(RateLimit::per_second(4));
+
+        limiter.record_rate_limited(Some(u64::MAX));
+        assert_eq!(
+            acquire(&limiter, 1).await,
+            MAX_RETRY_AFTER + Duration::from_millis(500)
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Retry policy for the Podbean API client.
+//!
+//! This module defines [`RetryPolicy`], which decides whether a failed
+//! request is sent again and how long to wait before doing so.
+
+use crate::PodbeanError;
+use reqwest::Method;
+use std::time::Duration;
+
+/// Policy for retrying failed API requests with exponential backoff.
+///
+/// Errors for which [`PodbeanError::is_retryable`] holds are retried:
+/// network errors, `5xx` responses and rate limit errors. The
+/// wait doubles after every attempt, up to a maximum, and is randomized
+/// with jitter so that concurrent clients do not retry in lockstep. A
+/// `Retry-After` sent with a rate limit error takes precedence over the
+/// backoff; if it asks for a longer wait than the maximum backoff, the
+/// error is returned instead of waiting.
+///
+/// By default only idempotent requests (`GET`, `PUT`, `DELETE`, ...) are
+/// retried after network and server errors. Non-idempotent requests such as
+/// publishing an episode are only retried after such errors when opting in
+/// with [`retry_non_idempotent`](RetryPolicy::retry_non_idempotent), since
+/// a request that timed out may still have been applied. Rate limit errors
+/// are retried for every method, as the server rejected the request
+/// without applying it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use podbean::{PodbeanClient, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let client = PodbeanClient::builder("your_client_id", "your_client_secret")
+///     .retry_policy(
+///         RetryPolicy::default()
+///             .max_attempts(5)
+///             .initial_backoff(Duration::from_secs(1)),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum number of attempts, including the first one.
+    ///
+    /// Zero is treated as one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the wait before the first retry.
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Sets the upper bound for the wait between attempts.
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Enables or disables randomized jitter on the backoff.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Allows retrying non-idempotent requests such as `POST` after network
+    /// and server errors.
+    pub fn retry_non_idempotent(mut self, retry: bool) -> Self {
+        self.retry_non_idempotent = retry;
+        self
+    }
+
+    /// Returns how long to wait before retrying, or `None` to give up.
+    ///
+    /// `attempt` is the number of the attempt that just failed, starting at one.
+    pub(crate) fn retry_delay(
+        &self,
+        method: &Method,
+        attempt: u32,
+        error: &PodbeanError,
+    ) -> Option<Duration> {
+        let rate_limited = matches!(error, PodbeanError::RateLimitError { .. });
+
+        if attempt >= self.max_attempts
+            || !(rate_limited || method.is_idempotent() || self.retry_non_idempotent)
+            || !error.is_retryable()
+        {
+            return None;
+        }
+
+        if let PodbeanError::RateLimitError {
+            retry_after: Some(seconds),
+        } = error
+        {
+            let retry_after = Duration::from_secs(*seconds);
+            return (retry_after <= self.max_backoff).then_some(retry_after);
+        }
+
+        Some(self.backoff(attempt))
+    }
+
+    /// Computes the backoff after the given failed attempt.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            // Equal jitter: keep half of the backoff and randomize the rest.
+            let half = backoff / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts for idempotent requests, starting at 500ms and
+    /// capped at 30 seconds, with jitter.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+/// Returns a random number in `[0, 1)`, or `0.5` if the operating system
+/// cannot provide randomness.
+fn random_fraction() -> f64 {
+    match getrandom::u64() {
+        Ok(random) => (random >> 11) as f64 / (1u64 << 53) as f64,
+        Err(_) => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ApiError;
+    use reqwest::StatusCode;
+    use url::Url;
+
+    const RATE_LIMITED: PodbeanError = PodbeanError::RateLimitError { retry_after: None };
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy::default()
+            .max_attempts(10)
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(30))
+            .jitter(false)
+    }
+
+    fn retry_after(seconds: u64) -> PodbeanError {
+        PodbeanError::RateLimitError {
+            retry_after: Some(seconds),
+        }
+    }
+
+    #[test]
+    fn doubles_the_backoff_up_to_the_maximum() {
+        let delays: Vec<_> = (1..=7)
+            .map(|attempt| policy().retry_delay(&Method::GET, attempt, &RATE_LIMITED))
+            .collect();
+
+        assert_eq!(
+            delays,
+            [1, 2, 4, 8, 16, 30, 30].map(|seconds| Some(Duration::from_secs(seconds)))
+        );
+    }
+
+    #[test]
+    fn keeps_jitter_within_half_of_the_backoff() {
+        let policy = policy().jitter(true);
+
+        for attempt in 1..=6 {
+            let backoff = policy.clone().jitter(false).backoff(attempt);
+            for _ in 0..100 {
+                let delay = policy
+                    .retry_delay(&Method::GET, attempt, &RATE_LIMITED)
+                    .unwrap();
+                assert!(delay >= backoff / 2 && delay <= backoff);
+            }
+        }
+    }
+
+    #[test]
+    fn honours_retry_after_up_to_the_maximum_backoff() {
+        let delay = |seconds| policy().retry_delay(&Method::GET, 1, &retry_after(seconds));
+
+        assert_eq!(delay(0), Some(Duration::ZERO));
+        assert_eq!(delay(12), Some(Duration::from_secs(12)));
+        assert_eq!(delay(30), Some(Duration::from_secs(30)));
+        assert_eq!(delay(31), None);
+        assert_eq!(delay(u64::MAX), None);
+    }
+
+    #[test]
+    fn does_not_overflow_after_many_attempts() {
+        let policy = policy().max_attempts(u32::MAX).max_backoff(Duration::MAX);
+
+        assert_eq!(
+            policy.retry_delay(&Method::GET, u32::MAX - 1, &RATE_LIMITED),
+            Some(Duration::from_secs(1 << 16))
+        );
+    }
+
+    #[test]
+    fn gives_up_after_the_last_attempt_or_on_other_errors() {
+        let policy = policy().max_attempts(3);
+
+        assert!(policy.retry_delay(&Method::GET, 2, &RATE_LIMITED).is_some());
+        assert!(policy.retry_delay(&Method::GET, 3, &RATE_LIMITED).is_none());
+        assert!(
+            policy
+                .retry_delay(&Method::GET, 1, &PodbeanError::AuthError(String::new()))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn retries_non_idempotent_requests_only_when_enabled() {
+        let url = Url::parse("https://api.podbean.com/v1/episodes").unwrap();
+        let server_error = PodbeanError::ApiError(Box::new(ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Method::POST,
+            &url,
+            String::new(),
+        )));
+
+        assert!(
+            policy()
+                .retry_delay(&Method::POST, 1, &server_error)
+                .is_none()
+        );
+        assert!(
+            policy()
+                .retry_non_idempotent(true)
+                .retry_delay(&Method::POST, 1, &server_error)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn retries_rate_limited_requests_of_any_method() {
+        assert_eq!(
+            policy().retry_delay(&Method::POST, 1, &RATE_LIMITED),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy().retry_delay(&Method::POST, 1, &retry_after(5)),
+            Some(Duration::from_secs(5))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! OAuth scopes for the Podbean API client.
+//!
+//! This module defines [`Scope`], a permission an access token can grant,
+//! and [`Scopes`], the set of them requested in the authorization dialog
+//! and kept on each token, along with the scope each endpoint requires.
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::convert::Infallible;
+use std::fmt;
+use std::str::FromStr;
+
+/// A permission granted to an access token.
+///
+/// Values not known to this crate are kept in [`Scope::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Scope {
+    /// Read podcast details
+    PodcastRead,
+    /// Update podcast details
+    PodcastUpdate,
+    /// Read episodes and media files
+    EpisodeRead,
+    /// Upload media files and publish, update or delete episodes
+    EpisodePublish,
+    /// Manage the private members of a podcast
+    PrivateMembers,
+    /// Scope not known to this crate
+    Unknown(String),
+}
+
+impl Scope {
+    /// Gets the scope Podbean documents for an API endpoint, if any.
+    ///
+    /// `endpoint` is the path relative to the API base URL, e.g.
+    /// `/episodes`. Endpoints without a documented scope, such as updating
+    /// or deleting an episode, return `None` and are left to the server to
+    /// check.
+    pub(crate) fn required_for(method: &Method, endpoint: &str) -> Option<Scope> {
+        let resource = endpoint.trim_start_matches('/').split('/').next()?;
+
+        match (method.as_str(), resource) {
+            ("GET", "podcasts") => Some(Scope::PodcastRead),
+            ("PUT", "podcasts") => Some(Scope::PodcastUpdate),
+            ("GET", "episodes") => Some(Scope::EpisodeRead),
+            ("POST", "episodes") => Some(Scope::EpisodePublish),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::PodcastRead => write!(f, "podcast_read"),
+            Scope::PodcastUpdate => write!(f, "podcast_update"),
+            Scope::EpisodeRead => write!(f, "episode_read"),
+            Scope::EpisodePublish => write!(f, "episode_publish"),
+            Scope::PrivateMembers => write!(f, "private_members"),
+            Scope::Unknown(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "podcast_read" => Scope::PodcastRead,
+            "podcast_update" => Scope::PodcastUpdate,
+            "episode_read" => Scope::EpisodeRead,
+            "episode_publish" => Scope::EpisodePublish,
+            "private_members" => Scope::PrivateMembers,
+            other => Scope::Unknown(other.to_string()),
+        })
+    }
+}
+
+/// A set of OAuth scopes.
+///
+/// Written as space-separated scope names, the form used by the OAuth
+/// dialog and token endpoints.
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::{Scope, Scopes};
+///
+/// let scopes = Scopes::from([Scope::EpisodeRead, Scope::EpisodePublish]);
+/// assert_eq!(scopes.to_string(), "episode_read episode_publish");
+///
+/// let granted: Scopes = "podcast_read episode_read".parse().unwrap();
+/// assert!(granted.contains(&Scope::EpisodeRead));
+/// assert!(!granted.contains(&Scope::EpisodePublish));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct Scopes(BTreeSet<Scope>);
+
+impl Scopes {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the set of every scope known to this crate.
+    pub fn all() -> Self {
+        Self::from([
+            Scope::PodcastRead,
+            Scope::PodcastUpdate,
+            Scope::EpisodeRead,
+            Scope::EpisodePublish,
+            Scope::PrivateMembers,
+        ])
+    }
+
+    /// Adds `scope` to the set.
+    pub fn with(mut self, scope: Scope) -> Self {
+        let _ = self.0.insert(scope);
+        self
+    }
+
+    /// Checks if the set contains `scope`.
+    pub fn contains(&self, scope: &Scope) -> bool {
+        self.0.contains(scope)
+    }
+
+    /// Checks if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the scopes in the set.
+    pub fn iter(&self) -> impl Iterator<Item = &Scope> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for Scopes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, scope) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", scope)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Scopes {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.split([' ', ','])
+            .filter(|name| !name.is_empty())
+            .map(|name| {
+                let Ok(scope) = name.parse();
+                scope
+            })
+            .collect())
+    }
+}
+
+impl FromIterator<Scope> for Scopes {
+    fn from_iter<I: IntoIterator<Item = Scope>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<const N: usize> From<[Scope; N]> for Scopes {
+    fn from(scopes: [Scope; N]) -> Self {
+        scopes.into_iter().collect()
+    }
+}
+
+impl From<String> for Scopes {
+    fn from(value: String) -> Self {
+        let Ok(scopes) = value.parse();
+        scopes
+    }
+}
+
+impl From<Scopes> for String {
+    fn from(value: Scopes) -> Self {
+        value.to_string()
+    }
+}
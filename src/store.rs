@@ -0,0 +1,236 @@
+//! Token persistence for the Podbean API client.
+//!
+//! This module defines the [`TokenStore`] trait used by [`PodbeanClient`]
+//! to restore a token at startup and to save it after every authorization
+//! or refresh, along with in-memory and JSON file implementations.
+//!
+//! [`PodbeanClient`]: crate::PodbeanClient
+
+use crate::PodbeanResult;
+use crate::types::AuthToken;
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Mutex, PoisonError};
+
+/// A boxed future returned by [`TokenStore`] methods.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Storage backend for persisting OAuth tokens.
+///
+/// The client calls [`load`](TokenStore::load) the first time it needs a
+/// token and has none in memory, [`save`](TokenStore::save) whenever a
+/// new token is obtained, and [`clear`](TokenStore::clear) once the token
+/// has been revoked.
+pub trait TokenStore: fmt::Debug + Send + Sync {
+    /// Loads the previously saved token, if any.
+    fn load(&self) -> BoxFuture<'_, PodbeanResult<Option<AuthToken>>>;
+
+    /// Saves the given token, replacing any previously saved one.
+    ///
+    /// An error does not fail the call that obtained the token, which stays
+    /// in use; the client logs it as a `tracing` warning.
+    fn save<'a>(&'a self, token: &'a AuthToken) -> BoxFuture<'a, PodbeanResult<()>>;
+
+    /// Removes the saved token, if any.
+    ///
+    /// The default implementation does nothing, so stores holding
+    /// credentials should override it.
+    fn clear(&self) -> BoxFuture<'_, PodbeanResult<()>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// A [`TokenStore`] that keeps the token in memory.
+///
+/// Useful for tests, or to share a token between clients that do not share
+/// token state.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: Mutex<Option<AuthToken>>,
+}
+
+impl MemoryTokenStore {
+    /// Creates an empty in-memory token store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> BoxFuture<'_, PodbeanResult<Option<AuthToken>>> {
+        let token = self
+            .token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        Box::pin(async move { Ok(token) })
+    }
+
+    fn save<'a>(&'a self, token: &'a AuthToken) -> BoxFuture<'a, PodbeanResult<()>> {
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = Some(token.clone());
+
+        Box::pin(async { Ok(()) })
+    }
+
+    fn clear(&self) -> BoxFuture<'_, PodbeanResult<()>> {
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) = None;
+
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// A [`TokenStore`] that persists the token as a JSON file.
+///
+/// The file is written to a temporary sibling first and then renamed into
+/// place, so a crash mid-write never leaves a truncated token behind.
+///
+/// On Unix the file is created readable and writable by its owner only
+/// (mode `0600`). On other platforms it inherits the permissions of its
+/// directory, so keep it in a directory only the user can read.
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Creates a token store backed by the JSON file at `path`.
+    ///
+    /// The file does not need to exist yet.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Gets the path of the backing file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> BoxFuture<'_, PodbeanResult<Option<AuthToken>>> {
+        Box::pin(async move {
+            match tokio::fs::read(&self.path).await {
+                Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
+    fn save<'a>(&'a self, token: &'a AuthToken) -> BoxFuture<'a, PodbeanResult<()>> {
+        Box::pin(async move {
+            let contents = serde_json::to_vec_pretty(token)?;
+            let mut temp_path = self.path.clone().into_os_string();
+            temp_path.push(".tmp");
+
+            // Start afresh so a stale temporary file cannot lend its
+            // permissions to the new token
+            match tokio::fs::remove_file(&temp_path).await {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+
+            let mut options = tokio::fs::OpenOptions::new();
+            let _ = options.write(true).create_new(true);
+            #[cfg(unix)]
+            let _ = options.mode(0o600);
+            drop(options.open(&temp_path).await?);
+
+            tokio::fs::write(&temp_path, contents).await?;
+            tokio::fs::rename(&temp_path, &self.path).await?;
+
+            Ok(())
+        })
+    }
+
+    fn clear(&self) -> BoxFuture<'_, PodbeanResult<()>> {
+        Box::pin(async move {
+            match tokio::fs::remove_file(&self.path).await {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Grant, TokenResponse};
+
+    fn token() -> AuthToken {
+        let response: TokenResponse = serde_json::from_str(
+            r#"{"access_token":"access","expires_in":3600,"refresh_token":"refresh"}"#,
+        )
+        .unwrap();
+        AuthToken::new(response, Grant::AuthorizationCode)
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "podbean-store-{}-{}.json",
+            name,
+            std::process::id()
+        ))
+    }
+
+    #[tokio::test]
+    async fn memory_store_round_trips_and_clears() {
+        let store = MemoryTokenStore::new();
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&token()).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.access_token(), "access");
+        assert_eq!(loaded.refresh_token(), Some("refresh"));
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn file_store_round_trips_and_clears() {
+        let store = FileTokenStore::new(temp_path("round-trip"));
+        let token = token();
+
+        // Saving again replaces the file
+        store.save(&token).await.unwrap();
+        store.save(&token).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.access_token(), "access");
+        assert_eq!(loaded.expires_at(), token.expires_at());
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+        store.clear().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn file_store_loads_nothing_from_a_missing_file() {
+        let store = FileTokenStore::new(temp_path("missing"));
+
+        assert!(store.load().await.unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_store_keeps_the_token_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store = FileTokenStore::new(temp_path("private"));
+        store.save(&token()).await.unwrap();
+
+        let mode = std::fs::metadata(store.path())
+            .unwrap()
+            .permissions()
+            .mode();
+        store.clear().await.unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,375 @@
+//! Time types for the Podbean API client.
+//!
+//! This module defines [`Timestamp`], the point in time used for every
+//! date the API returns or accepts, and the serde helpers that read
+//! Podbean's mixed date and duration formats.
+//!
+//! With the `time` feature enabled, timestamps convert to and from
+//! `time::OffsetDateTime`.
+
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use time::format_description::BorrowedFormatItem;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{OffsetDateTime, PrimitiveDateTime};
+
+/// The date format used by Podbean, such as `2024-03-01 12:30:00`.
+const PODBEAN_FORMAT: &[BorrowedFormatItem<'_>] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+
+/// A point in time with second precision, in UTC.
+///
+/// Podbean reports times as Unix timestamps in some places and as
+/// `YYYY-MM-DD HH:MM:SS` strings in others. Both are read into a
+/// `Timestamp`, which serializes back as a Unix timestamp.
+///
+/// Converts to and from [`SystemTime`], and with the `time` feature to and
+/// from `time::OffsetDateTime`.
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::Timestamp;
+/// use std::time::{Duration, SystemTime};
+///
+/// let timestamp = Timestamp::from_unix_timestamp(1_700_000_000);
+/// assert_eq!(timestamp.to_string(), "2023-11-14T22:13:20Z");
+///
+/// let tomorrow = Timestamp::from(SystemTime::now() + Duration::from_secs(86_400));
+/// assert!(tomorrow > Timestamp::now());
+///
+/// // Podbean's date strings are read as UTC
+/// let published: Timestamp = serde_json::from_str("\"2024-03-01 12:30:00\"").unwrap();
+/// assert_eq!(published, Timestamp::from_unix_timestamp(1_709_296_200));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Timestamp(i64);
+
+impl Timestamp {
+    /// Creates a timestamp from seconds since the Unix epoch.
+    pub fn from_unix_timestamp(seconds: i64) -> Self {
+        Self(seconds)
+    }
+
+    /// Gets the current time.
+    pub fn now() -> Self {
+        Self::from(SystemTime::now())
+    }
+
+    /// Gets the number of seconds since the Unix epoch.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.0
+    }
+
+    /// Parses a Unix timestamp, a Podbean date such as
+    /// `2024-03-01 12:30:00`, or an RFC 3339 date.
+    ///
+    /// Podbean dates carry no UTC offset and are read as UTC.
+    fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+
+        if let Ok(seconds) = input.parse::<i64>() {
+            return Some(Self(seconds));
+        }
+
+        let time = PrimitiveDateTime::parse(input, PODBEAN_FORMAT)
+            .map(PrimitiveDateTime::assume_utc)
+            .or_else(|_| OffsetDateTime::parse(input, &Rfc3339))
+            .ok()?;
+
+        Some(Self(time.unix_timestamp()))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    /// Formats the timestamp as RFC 3339 in UTC, or as seconds since the
+    /// Unix epoch if its year is outside 0 to 9999.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match OffsetDateTime::from_unix_timestamp(self.0)
+            .ok()
+            .and_then(|time| time.format(&Rfc3339).ok())
+        {
+            Some(formatted) => f.write_str(&formatted),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(after) => Self(i64::try_from(after.as_secs()).unwrap_or(i64::MAX)),
+            Err(before) => Self(-i64::try_from(before.duration().as_secs()).unwrap_or(i64::MAX)),
+        }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> Self {
+        let offset = Duration::from_secs(timestamp.0.unsigned_abs());
+
+        if timestamp.0 >= 0 {
+            UNIX_EPOCH + offset
+        } else {
+            UNIX_EPOCH - offset
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<OffsetDateTime> for Timestamp {
+    fn from(time: OffsetDateTime) -> Self {
+        Self(time.unix_timestamp())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<Timestamp> for OffsetDateTime {
+    /// Converts to an `OffsetDateTime` in UTC, saturating at the range
+    /// supported by the `time` crate.
+    fn from(timestamp: Timestamp) -> Self {
+        OffsetDateTime::from_unix_timestamp(timestamp.0).unwrap_or_else(|_| {
+            if timestamp.0 < 0 {
+                PrimitiveDateTime::MIN.assume_utc()
+            } else {
+                PrimitiveDateTime::MAX.assume_utc()
+            }
+        })
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_any(TimestampVisitor)?
+            .ok_or_else(|| de::Error::custom("missing timestamp"))
+    }
+}
+
+/// Deserializes an optional timestamp, reading `null` and empty strings as
+/// `None`.
+pub(crate) fn deserialize_optional<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(TimestampVisitor)
+}
+
+/// Reads a timestamp from a number of seconds or a date string.
+struct TimestampVisitor;
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = Option<Timestamp>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a Unix timestamp or a date string")
+    }
+
+    fn visit_i64<E: de::Error>(self, seconds: i64) -> Result<Self::Value, E> {
+        Ok(Some(Timestamp(seconds)))
+    }
+
+    fn visit_u64<E: de::Error>(self, seconds: u64) -> Result<Self::Value, E> {
+        i64::try_from(seconds)
+            .map(|seconds| Some(Timestamp(seconds)))
+            .map_err(|_| E::custom("timestamp out of range"))
+    }
+
+    fn visit_f64<E: de::Error>(self, seconds: f64) -> Result<Self::Value, E> {
+        Ok(Some(Timestamp(seconds as i64)))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        if value.trim().is_empty() {
+            return Ok(None);
+        }
+
+        Timestamp::parse(value)
+            .map(Some)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Serde helpers for durations sent as whole seconds.
+///
+/// Besides numbers, `HH:MM:SS` and `MM:SS` strings are accepted. `null` and
+/// empty strings are read as `None`.
+pub(crate) mod duration_secs {
+    use serde::Serializer;
+    use serde::de::{self, Deserializer, Visitor};
+    use std::fmt;
+    use std::time::Duration;
+
+    /// Serializes a duration as whole seconds.
+    pub(crate) fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_some(&duration.as_secs()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    /// Deserializes a duration from seconds or a clock string.
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        deserializer.deserialize_option(DurationVisitor)
+    }
+
+    /// Reads a duration from a number of seconds or a clock string.
+    struct DurationVisitor;
+
+    impl<'de> Visitor<'de> for DurationVisitor {
+        type Value = Option<Duration>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a number of seconds or an HH:MM:SS string")
+        }
+
+        fn visit_u64<E: de::Error>(self, seconds: u64) -> Result<Self::Value, E> {
+            Ok(Some(Duration::from_secs(seconds)))
+        }
+
+        fn visit_i64<E: de::Error>(self, seconds: i64) -> Result<Self::Value, E> {
+            u64::try_from(seconds)
+                .map(|seconds| Some(Duration::from_secs(seconds)))
+                .map_err(|_| E::invalid_value(de::Unexpected::Signed(seconds), &self))
+        }
+
+        fn visit_f64<E: de::Error>(self, seconds: f64) -> Result<Self::Value, E> {
+            Duration::try_from_secs_f64(seconds)
+                .map(Some)
+                .map_err(|_| E::invalid_value(de::Unexpected::Float(seconds), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+            let value = value.trim();
+
+            if value.is_empty() {
+                return Ok(None);
+            }
+
+            let mut seconds = 0u64;
+            for part in value.split(':') {
+                let part = part
+                    .parse::<u64>()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))?;
+                seconds = seconds.saturating_mul(60).saturating_add(part);
+            }
+
+            Ok(Some(Duration::from_secs(seconds)))
+        }
+
+        fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+            Ok(None)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2024-03-01 12:30:00 UTC.
+    const MARCH_FIRST: i64 = 1_709_296_200;
+
+    fn parse(input: &str) -> Option<i64> {
+        Timestamp::parse(input).map(|timestamp| timestamp.unix_timestamp())
+    }
+
+    #[test]
+    fn parses_podbean_and_rfc_3339_dates() {
+        assert_eq!(parse("2024-03-01 12:30:00"), Some(MARCH_FIRST));
+        assert_eq!(parse("2024-03-01T12:30:00Z"), Some(MARCH_FIRST));
+        assert_eq!(parse("2024-03-01T14:30:00+02:00"), Some(MARCH_FIRST));
+        assert_eq!(parse("2024-03-01T07:00:00.999-05:30"), Some(MARCH_FIRST));
+        assert_eq!(parse("2024-03-01T12:30:00+2"), None);
+        assert_eq!(parse("2024-03-01 12:30:00 tomorrow"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert_eq!(parse("2023-02-29 00:00:00"), None);
+        assert_eq!(parse("2024-02-29 00:00:00"), Some(1_709_164_800));
+        assert_eq!(parse("2024-04-31 00:00:00"), None);
+        assert_eq!(parse("2024-13-01 00:00:00"), None);
+        assert_eq!(parse("2024-03-01 24:00:00"), None);
+    }
+
+    #[test]
+    fn handles_times_before_the_epoch() {
+        assert_eq!(parse("1969-12-31 23:59:59"), Some(-1));
+        assert_eq!(parse("1900-01-01T00:00:00Z"), Some(-2_208_988_800));
+        assert_eq!(parse("-86400"), Some(-86_400));
+        assert_eq!(
+            Timestamp::from_unix_timestamp(-1).to_string(),
+            "1969-12-31T23:59:59Z"
+        );
+        assert_eq!(
+            Timestamp::from(UNIX_EPOCH - Duration::from_secs(60)),
+            Timestamp::from_unix_timestamp(-60)
+        );
+    }
+
+    #[test]
+    fn reads_empty_strings_as_missing() {
+        assert_eq!(parse(""), None);
+        assert!(serde_json::from_str::<Timestamp>("\"\"").is_err());
+
+        let optional = |json: &str| {
+            deserialize_optional(&mut serde_json::Deserializer::from_str(json)).unwrap()
+        };
+        assert_eq!(optional("\"\""), None);
+        assert_eq!(optional("null"), None);
+        assert_eq!(
+            optional("\"2024-03-01 12:30:00\""),
+            Some(Timestamp::from_unix_timestamp(MARCH_FIRST))
+        );
+    }
+
+    #[test]
+    fn displays_as_rfc_3339() {
+        let timestamp = Timestamp::from_unix_timestamp(MARCH_FIRST);
+
+        assert_eq!(timestamp.to_string(), "2024-03-01T12:30:00Z");
+        assert_eq!(parse(&timestamp.to_string()), Some(MARCH_FIRST));
+        assert_eq!(
+            Timestamp::from_unix_timestamp(i64::MAX).to_string(),
+            i64::MAX.to_string()
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! HTTP transport for the Podbean API client.
+//!
+//! This module defines the [`Transport`] trait, through which
+//! [`PodbeanClient`](crate::PodbeanClient) sends every HTTP request, and
+//! [`ReqwestTransport`], the default implementation. Tests can install a
+//! scripted transport to check requests and return canned responses
+//! without opening sockets.
+
+use crate::{BoxFuture, PodbeanResult};
+use bytes::Bytes;
+use futures_core::Stream;
+use reqwest::header::{AUTHORIZATION, HeaderMap};
+use reqwest::{Body, Client, Method, StatusCode};
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::time::Duration;
+use url::Url;
+
+/// Names of parameters carrying credentials, redacted from `Debug` output.
+const SENSITIVE_PARAMS: &[&str] = &[
+    "client_secret",
+    "code",
+    "code_verifier",
+    "refresh_token",
+    "access_token",
+    "token",
+];
+
+/// Placeholder printed instead of a credential.
+const REDACTED: &str = "[redacted]";
+
+/// A streamed request body, such as a media file being uploaded.
+pub type BodyStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+/// Body of an [`HttpRequest`].
+pub enum RequestBody {
+    /// No body
+    Empty,
+
+    /// URL-encoded form parameters
+    Form(Vec<(String, String)>),
+
+    /// A stream of bytes, sent with the `Content-Length` header of the
+    /// request
+    Stream(BodyStream),
+}
+
+impl fmt::Debug for RequestBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestBody::Empty => f.write_str("Empty"),
+            RequestBody::Form(params) => {
+                let params: Vec<(&str, &str)> = params
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), redact_param(key, value)))
+                    .collect();
+                f.debug_tuple("Form").field(&params).finish()
+            }
+            RequestBody::Stream(_) => f.write_str("Stream"),
+        }
+    }
+}
+
+/// An HTTP request to be sent by a [`Transport`].
+///
+/// Query parameters are already encoded into the URL. The `Debug` output
+/// redacts credentials, such as the `Authorization` header, client secrets
+/// and tokens, so requests can be logged safely.
+pub struct HttpRequest {
+    /// HTTP method
+    pub method: Method,
+
+    /// Full URL, including the query string
+    pub url: Url,
+
+    /// Request headers, e.g. `Authorization`
+    pub headers: HeaderMap,
+
+    /// Request body
+    pub body: RequestBody,
+
+    /// Timeout overriding the transport's default, used for uploads
+    pub timeout: Option<Duration>,
+}
+
+impl fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut url = self.url.clone();
+        if url.query().is_some() {
+            let pairs: Vec<(String, String)> = self
+                .url
+                .query_pairs()
+                .map(|(key, value)| {
+                    let value = redact_param(&key, &value).to_string();
+                    (key.into_owned(), value)
+                })
+                .collect();
+            let _ = url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if name == AUTHORIZATION || value.is_sensitive() {
+                    REDACTED
+                } else {
+                    value.to_str().unwrap_or("[binary]")
+                };
+                (name.as_str(), value)
+            })
+            .collect();
+
+        f.debug_struct("HttpRequest")
+            .field("method", &self.method)
+            .field("url", &url.as_str())
+            .field("headers", &headers)
+            .field("body", &self.body)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Returns `value`, or a placeholder if `key` names a credential.
+fn redact_param<'a>(key: &str, value: &'a str) -> &'a str {
+    if SENSITIVE_PARAMS.contains(&key) {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+impl HttpRequest {
+    /// Gets the first query parameter named `key`.
+    pub fn query_param(&self, key: &str) -> Option<String> {
+        self.url
+            .query_pairs()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.into_owned())
+    }
+
+    /// Gets the first form parameter named `key`.
+    pub fn form_param(&self, key: &str) -> Option<&str> {
+        match &self.body {
+            RequestBody::Form(params) => params
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// An HTTP response returned by a [`Transport`].
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// HTTP status code
+    pub status: StatusCode,
+
+    /// Response headers, e.g. `Retry-After`
+    pub headers: HeaderMap,
+
+    /// Complete response body
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    /// Creates a response without headers.
+    pub fn new(status: StatusCode, body: impl Into<Bytes>) -> Self {
+        Self {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Sends HTTP requests on behalf of a [`PodbeanClient`](crate::PodbeanClient).
+///
+/// Rate limiting, retries, token handling and error parsing all happen in
+/// the client, so an implementation only needs to perform a single
+/// exchange. Error responses are returned as responses, not as errors.
+///
+/// # Examples
+///
+/// A transport answering every request with the same response:
+///
+/// ```rust
+/// use podbean::{BoxFuture, HttpRequest, HttpResponse, PodbeanResult, Transport};
+/// use reqwest::StatusCode;
+///
+/// #[derive(Debug)]
+/// struct NotFound;
+///
+/// impl Transport for NotFound {
+///     fn send(&self, _request: HttpRequest) -> BoxFuture<'_, PodbeanResult<HttpResponse>> {
+///         Box::pin(async {
+///             Ok(HttpResponse::new(
+///                 StatusCode::NOT_FOUND,
+///                 r#"{"error":"not_found","error_description":"Episode not found"}"#,
+///             ))
+///         })
+///     }
+/// }
+///
+/// let client = podbean::PodbeanClient::builder("id", "secret")
+///     .transport(NotFound)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait Transport: fmt::Debug + Send + Sync {
+    /// Sends `request` and reads the complete response.
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, PodbeanResult<HttpResponse>>;
+}
+
+/// The default [`Transport`], backed by a `reqwest::Client`.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    /// Sends requests with the given client.
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, PodbeanResult<HttpResponse>> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(request.method, request.url)
+                .headers(request.headers);
+
+            builder = match request.body {
+                RequestBody::Empty => builder,
+                RequestBody::Form(params) => builder.form(&params),
+                RequestBody::Stream(stream) => builder.body(Body::wrap_stream(stream)),
+            };
+
+            if let Some(timeout) = request.timeout {
+                builder = builder.timeout(timeout);
+            }
+
+            let response = builder.send().await?;
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn debug_output_redacts_credentials() {
+        let mut headers = HeaderMap::new();
+        let _ = headers.insert(
+            AUTHORIZATION,
+            HeaderValue::from_static("Bearer SUPERSECRETTOKEN"),
+        );
+        let request = HttpRequest {
+            method: Method::POST,
+            url: Url::parse(
+                "https://api.podbean.com/v1/oauth/token?access_token=QUERYTOKEN&limit=5",
+            )
+            .unwrap(),
+            headers,
+            body: RequestBody::Form(vec![
+                ("grant_type".to_string(), "refresh_token".to_string()),
+                ("client_secret".to_string(), "MYCLIENTSECRET".to_string()),
+                ("refresh_token".to_string(), "MYREFRESHTOKEN".to_string()),
+            ]),
+            timeout: None,
+        };
+
+        let output = format!("{:?}", request);
+
+        for secret in [
+            "SUPERSECRETTOKEN",
+            "QUERYTOKEN",
+            "MYCLIENTSECRET",
+            "MYREFRESHTOKEN",
+        ] {
+            assert!(!output.contains(secret), "{} leaked in {}", secret, output);
+        }
+        assert!(output.contains("refresh_token"));
+        assert!(output.contains("limit=5"));
+    }
+}
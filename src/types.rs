@@ -3,17 +3,62 @@
 //! This module defines the various data structures used to represent
 //! Podbean API resources and responses.
 
+use crate::id::{EpisodeId, MediaKey, PodcastId};
+use crate::scope::{Scope, Scopes};
+use crate::timestamp::{self, Timestamp};
 use core::fmt;
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::convert::Infallible;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A string holding a credential, such as a client secret or token.
+///
+/// The value is redacted from `Debug` output and only available through
+/// [`expose_secret`](SecretString::expose_secret). It still serializes as
+/// the plain value so tokens can be persisted.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wraps a credential.
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Gets the underlying credential.
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
 
 /// Response from OAuth token endpoint.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize)]
 pub struct TokenResponse {
     /// OAuth access token
-    pub access_token: String,
+    pub access_token: SecretString,
 
     /// Token type (usually "Bearer")
+    #[serde(default = "default_token_type")]
     pub token_type: String,
 
     /// Token validity in seconds
@@ -23,66 +68,215 @@ pub struct TokenResponse {
     pub scope: Option<String>,
 
     /// Refresh token for obtaining a new access token
-    pub refresh_token: Option<String>,
+    pub refresh_token: Option<SecretString>,
+}
+
+/// OAuth grant an authentication token was obtained with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Grant {
+    /// Authorization code exchanged after user authorization
+    AuthorizationCode,
+    /// App-level client credentials
+    ClientCredentials,
 }
 
 /// Authentication token with metadata.
-#[derive(Debug, Clone)]
-pub(crate) struct AuthToken {
-    access_token: String,
+///
+/// The expiry is kept as an absolute wall-clock time, so a token can be
+/// persisted with a [`TokenStore`](crate::TokenStore) and restored by a
+/// later process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthToken {
+    access_token: SecretString,
     token_type: String,
-    expires_in: u64,
-    // scope: Option<String>,
-    refresh_token: Option<String>,
-    created_at: Instant,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scopes: Option<Scopes>,
+    refresh_token: Option<SecretString>,
+    /// Expiry as seconds since the Unix epoch
+    expires_at: u64,
+    grant: Grant,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    podcast_id: Option<PodcastId>,
 }
 
 impl AuthToken {
+    /// Creates a token from a token endpoint response.
+    pub(crate) fn new(response: TokenResponse, grant: Grant) -> Self {
+        Self {
+            access_token: response.access_token,
+            token_type: response.token_type,
+            scopes: response.scope.map(Scopes::from),
+            refresh_token: response.refresh_token,
+            expires_at: unix_now() + response.expires_in,
+            grant,
+            podcast_id: None,
+        }
+    }
+
+    /// Marks the token as scoped to a single podcast.
+    pub(crate) fn for_podcast(mut self, podcast_id: PodcastId) -> Self {
+        self.podcast_id = Some(podcast_id);
+        self
+    }
+
     /// Checks if the token is expired.
     ///
     /// Considers a token expired if it has less than 5 minutes of validity left.
-    pub(crate) fn is_expired(&self) -> bool {
-        let now = Instant::now();
-        let elapsed = now.duration_since(self.created_at);
-
+    pub fn is_expired(&self) -> bool {
         // Consider token expired if less than 5 minutes remaining
-        elapsed.as_secs() + 300 > self.expires_in
+        unix_now() + 300 > self.expires_at
+    }
+
+    /// Gets the time at which the token expires.
+    pub fn expires_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.expires_at)
     }
 
     /// Gets the access token string.
-    pub(crate) fn access_token(&self) -> &str {
-        &self.access_token
+    pub fn access_token(&self) -> &str {
+        self.access_token.expose_secret()
     }
 
     /// Gets the token type.
-    pub(crate) fn token_type(&self) -> &str {
+    pub fn token_type(&self) -> &str {
         &self.token_type
     }
 
     /// Gets the refresh token, if any.
-    pub(crate) fn refresh_token(&self) -> Option<&str> {
-        self.refresh_token.as_deref()
+    pub fn refresh_token(&self) -> Option<&str> {
+        self.refresh_token.as_ref().map(SecretString::expose_secret)
+    }
+
+    /// Checks if a new token can be obtained without user interaction.
+    pub(crate) fn is_renewable(&self) -> bool {
+        self.refresh_token.is_some() || self.grant == Grant::ClientCredentials
+    }
+
+    /// Carries over the refresh token and scopes of `previous` if none
+    /// were issued.
+    pub(crate) fn inherit(&mut self, previous: &AuthToken) {
+        if self.refresh_token.is_none() {
+            self.refresh_token = previous.refresh_token.clone();
+        }
+
+        if self.scopes.is_none() {
+            self.scopes = previous.scopes.clone();
+        }
+    }
+
+    /// Gets the scopes granted to the token, if the token endpoint
+    /// reported them.
+    pub fn scopes(&self) -> Option<&Scopes> {
+        self.scopes.as_ref()
+    }
+
+    /// Checks if the token grants `scope`.
+    ///
+    /// Tokens whose scopes were not reported are assumed to grant every
+    /// scope.
+    pub fn has_scope(&self, scope: &Scope) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.contains(scope))
+    }
+
+    /// Gets the grant the token was obtained with.
+    pub fn grant(&self) -> Grant {
+        self.grant
+    }
+
+    /// Gets the podcast the token is scoped to, if it was obtained with
+    /// [`PodbeanClient::authorize_podcasts`](crate::PodbeanClient::authorize_podcasts).
+    pub fn podcast_id(&self) -> Option<&PodcastId> {
+        self.podcast_id.as_ref()
     }
 }
 
-impl From<TokenResponse> for AuthToken {
-    fn from(response: TokenResponse) -> Self {
+/// Details of an access token, as reported by Podbean.
+///
+/// Returned by [`PodbeanClient::debug_token`](crate::PodbeanClient::debug_token)
+/// and [`PodbeanClient::introspect_token`](crate::PodbeanClient::introspect_token).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "TokenInfoResponse")]
+pub struct TokenInfo {
+    /// Whether Podbean still accepts the token
+    pub is_valid: bool,
+
+    /// Podcast the token grants access to
+    pub podcast_id: Option<PodcastId>,
+
+    /// OAuth scopes granted to the token
+    pub scopes: Option<Scopes>,
+
+    /// Time at which the token expires
+    pub expires_at: Option<Timestamp>,
+}
+
+/// Response from the debug token endpoint.
+///
+/// The expiry is reported either as an absolute time or as a number of
+/// seconds from now.
+#[derive(Debug, Deserialize)]
+struct TokenInfoResponse {
+    #[serde(default = "default_is_valid")]
+    is_valid: bool,
+    #[serde(default)]
+    podcast_id: Option<PodcastId>,
+    #[serde(default)]
+    scope: Option<String>,
+    #[serde(default, deserialize_with = "timestamp::deserialize_optional")]
+    expires_at: Option<Timestamp>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+impl From<TokenInfoResponse> for TokenInfo {
+    fn from(response: TokenInfoResponse) -> Self {
+        let expires_at = response.expires_at.or_else(|| {
+            let expires_in = i64::try_from(response.expires_in?).unwrap_or(i64::MAX);
+            let now = Timestamp::now().unix_timestamp();
+            Some(Timestamp::from_unix_timestamp(
+                now.saturating_add(expires_in),
+            ))
+        });
+
         Self {
-            access_token: response.access_token,
-            token_type: response.token_type,
-            expires_in: response.expires_in,
-            // scope: response.scope,
-            refresh_token: response.refresh_token,
-            created_at: Instant::now(),
+            is_valid: response.is_valid,
+            podcast_id: response.podcast_id.filter(|id| !id.as_str().is_empty()),
+            scopes: response
+                .scope
+                .filter(|scope| !scope.is_empty())
+                .map(Scopes::from),
+            expires_at,
         }
     }
 }
 
+/// A successful debug token response without `is_valid` describes a valid
+/// token.
+fn default_is_valid() -> bool {
+    true
+}
+
+/// Token type assumed when the token endpoint does not name one.
+fn default_token_type() -> String {
+    "Bearer".to_string()
+}
+
+/// Returns the current wall-clock time as seconds since the Unix epoch.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 /// Represents a media item in Podbean.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MediaItem {
     /// Unique identifier for the media
-    pub media_key: String,
+    pub media_key: MediaKey,
 
     /// Title of the media
     pub title: String,
@@ -90,8 +284,8 @@ pub struct MediaItem {
     /// Description or content
     pub content: String,
 
-    /// Status (e.g., "finished", "transcoding")
-    pub status: String,
+    /// Processing status
+    pub status: MediaStatus,
 
     /// URL to the media file
     pub media_url: String,
@@ -103,13 +297,16 @@ pub struct MediaItem {
     pub player_url: Option<String>,
 
     /// When the media was published
-    pub publish_time: Option<String>,
+    #[serde(default, deserialize_with = "timestamp::deserialize_optional")]
+    pub publish_time: Option<Timestamp>,
 
     /// When the media was created
-    pub created_at: String,
+    #[serde(default, deserialize_with = "timestamp::deserialize_optional")]
+    pub created_at: Option<Timestamp>,
 
-    /// Duration in seconds
-    pub duration: Option<u64>,
+    /// Length of the media
+    #[serde(default, with = "timestamp::duration_secs")]
+    pub duration: Option<Duration>,
 }
 
 /// Response for a list of media items.
@@ -127,10 +324,10 @@ pub struct MediaListResponse {
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Episode {
     /// Unique identifier for the episode
-    pub id: String,
+    pub id: EpisodeId,
 
     /// Unique identifier for the podcast
-    pub podcast_id: String,
+    pub podcast_id: PodcastId,
 
     /// Episode title
     pub title: String,
@@ -147,18 +344,20 @@ pub struct Episode {
     /// Permalink URL for the episode
     pub permalink_url: String,
 
-    /// When the episode was published
-    pub publish_time: u64,
+    /// When the episode was published; `None` for drafts
+    #[serde(default, deserialize_with = "timestamp::deserialize_optional")]
+    pub publish_time: Option<Timestamp>,
 
-    /// Duration in seconds
-    pub duration: Option<u64>,
+    /// Length of the episode
+    #[serde(default, with = "timestamp::duration_secs")]
+    pub duration: Option<Duration>,
 
-    /// Publication status (e.g., "published", "draft")
-    pub status: String,
+    /// Publication status
+    pub status: EpisodeStatus,
 
-    /// Type of episode (e.g., "public", "premium")
+    /// Type of episode
     #[serde(rename = "type")]
-    pub episode_type: String,
+    pub episode_type: EpisodeType,
 
     /// URL to the transcript
     pub transcripts_url: Option<String>,
@@ -178,7 +377,7 @@ pub struct EpisodeListResponse {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Podcast {
     /// Unique identifier for the podcast
-    pub podcast_id: String,
+    pub podcast_id: PodcastId,
 
     /// Podcast title
     pub title: String,
@@ -210,14 +409,20 @@ pub struct PodcastListResponse {
 }
 
 /// Represents a episode type.
-#[derive(Debug)]
+///
+/// Values not known to this crate are kept in [`EpisodeType::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum EpisodeType {
     /// Public episode
+    #[default]
     Public,
     /// Premium episode
     Premium,
     /// Private episode
     Private,
+    /// Episode type not known to this crate
+    Unknown(String),
 }
 
 impl fmt::Display for EpisodeType {
@@ -226,19 +431,64 @@ impl fmt::Display for EpisodeType {
             EpisodeType::Public => write!(f, "public"),
             EpisodeType::Premium => write!(f, "premium"),
             EpisodeType::Private => write!(f, "private"),
+            EpisodeType::Unknown(value) => write!(f, "{}", value),
         }
     }
 }
 
+impl FromStr for EpisodeType {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "public" => EpisodeType::Public,
+            "premium" => EpisodeType::Premium,
+            "private" => EpisodeType::Private,
+            other => EpisodeType::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl From<String> for EpisodeType {
+    fn from(value: String) -> Self {
+        let Ok(episode_type) = value.parse();
+        episode_type
+    }
+}
+
+impl From<EpisodeType> for String {
+    fn from(value: EpisodeType) -> Self {
+        value.to_string()
+    }
+}
+
 /// Episode status
-#[derive(Debug)]
+///
+/// Values not known to this crate are kept in [`EpisodeStatus::Unknown`].
+///
+/// # Examples
+///
+/// ```rust
+/// use podbean::EpisodeStatus;
+///
+/// let status: EpisodeStatus = "publish".parse().unwrap();
+/// assert_eq!(status, EpisodeStatus::Publish);
+///
+/// let status: EpisodeStatus = "scheduled".parse().unwrap();
+/// assert_eq!(status, EpisodeStatus::Unknown("scheduled".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum EpisodeStatus {
     /// Publish episode
     Publish,
     /// Draft episode
+    #[default]
     Draft,
     /// Future episode
     Future,
+    /// Episode status not known to this crate
+    Unknown(String),
 }
 
 impl fmt::Display for EpisodeStatus {
@@ -247,6 +497,121 @@ impl fmt::Display for EpisodeStatus {
             EpisodeStatus::Publish => write!(f, "publish"),
             EpisodeStatus::Draft => write!(f, "draft"),
             EpisodeStatus::Future => write!(f, "future"),
+            EpisodeStatus::Unknown(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for EpisodeStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "publish" => EpisodeStatus::Publish,
+            "draft" => EpisodeStatus::Draft,
+            "future" => EpisodeStatus::Future,
+            other => EpisodeStatus::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl From<String> for EpisodeStatus {
+    fn from(value: String) -> Self {
+        let Ok(status) = value.parse();
+        status
+    }
+}
+
+impl From<EpisodeStatus> for String {
+    fn from(value: EpisodeStatus) -> Self {
+        value.to_string()
+    }
+}
+
+/// Processing status of a media file.
+///
+/// Values not known to this crate are kept in [`MediaStatus::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum MediaStatus {
+    /// The media file is ready to be used
+    Finished,
+    /// The media file is still being transcoded
+    Transcoding,
+    /// Media status not known to this crate
+    Unknown(String),
+}
+
+impl fmt::Display for MediaStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MediaStatus::Finished => write!(f, "finished"),
+            MediaStatus::Transcoding => write!(f, "transcoding"),
+            MediaStatus::Unknown(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for MediaStatus {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "finished" => MediaStatus::Finished,
+            "transcoding" => MediaStatus::Transcoding,
+            other => MediaStatus::Unknown(other.to_string()),
+        })
+    }
+}
+
+impl From<String> for MediaStatus {
+    fn from(value: String) -> Self {
+        let Ok(status) = value.parse();
+        status
+    }
+}
+
+impl From<MediaStatus> for String {
+    fn from(value: MediaStatus) -> Self {
+        value.to_string()
+    }
+}
+
+/// Episode type as reported to Apple Podcasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppleEpisodeType {
+    /// Full episode
+    Full,
+    /// Trailer
+    Trailer,
+    /// Bonus content
+    Bonus,
+}
+
+impl fmt::Display for AppleEpisodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppleEpisodeType::Full => write!(f, "full"),
+            AppleEpisodeType::Trailer => write!(f, "trailer"),
+            AppleEpisodeType::Bonus => write!(f, "bonus"),
+        }
+    }
+}
+
+/// Explicit content rating of an episode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentExplicit {
+    /// Clean content
+    Clean,
+    /// Explicit content
+    Explicit,
+}
+
+impl fmt::Display for ContentExplicit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentExplicit::Clean => write!(f, "clean"),
+            ContentExplicit::Explicit => write!(f, "explicit"),
         }
     }
 }
@@ -263,12 +628,19 @@ pub enum MediaFormat {
     Ogg,
 }
 
-impl fmt::Display for MediaFormat {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl MediaFormat {
+    /// Gets the MIME type of the format.
+    pub(crate) fn content_type(&self) -> &'static str {
         match self {
-            MediaFormat::Mp3 => write!(f, "audio/mp3"),
-            MediaFormat::M4a => write!(f, "audio/m4a"),
-            MediaFormat::Ogg => write!(f, "audio/ogg"),
+            MediaFormat::Mp3 => "audio/mp3",
+            MediaFormat::M4a => "audio/m4a",
+            MediaFormat::Ogg => "audio/ogg",
         }
     }
 }
+
+impl fmt::Display for MediaFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.content_type())
+    }
+}
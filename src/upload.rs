@@ -0,0 +1,161 @@
+//! Options for media uploads.
+//!
+//! This module defines [`UploadOptions`], which adds progress reporting,
+//! cancellation, a dedicated timeout and the podcast to upload for to
+//! presigned media uploads.
+
+use crate::PodcastId;
+use bytes::Bytes;
+use futures_core::Stream;
+use std::fmt;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Progress of a media upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Number of bytes sent so far
+    pub bytes_sent: u64,
+
+    /// Total number of bytes to send
+    pub total_bytes: u64,
+}
+
+/// Callback invoked with the progress of an upload.
+type ProgressCallback = Arc<dyn Fn(UploadProgress) + Send + Sync>;
+
+/// Options for a single media upload.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use podbean::{CancellationToken, MediaFormat, PodbeanClient, UploadOptions};
+/// # use std::time::Duration;
+/// # use tokio::runtime::Runtime;
+/// # let client = PodbeanClient::new("id", "secret").unwrap();
+/// # let rt = Runtime::new().unwrap();
+/// # rt.block_on(async {
+/// let cancel = CancellationToken::new();
+/// let options = UploadOptions::new()
+///     .on_progress(|progress| {
+///         println!("{} / {} bytes", progress.bytes_sent, progress.total_bytes);
+///     })
+///     .cancellation_token(cancel.clone())
+///     .timeout(Duration::from_secs(3600));
+///
+/// // Calling `cancel.cancel()` from another task aborts the upload.
+/// let media_key = client
+///     .upload_media_from_path_with("episode.mp3", MediaFormat::Mp3, options)
+///     .await
+///     .unwrap();
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct UploadOptions {
+    pub(crate) progress: Option<ProgressCallback>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) podcast_id: Option<PodcastId>,
+}
+
+impl UploadOptions {
+    /// Creates options with no progress reporting, cancellation, timeout
+    /// override or podcast.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Calls `callback` every time a chunk of the file has been sent.
+    ///
+    /// To report progress to another task, send the value through a channel
+    /// such as `tokio::sync::watch` from within the callback.
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(UploadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Aborts the upload with [`PodbeanError::Cancelled`] once `token` is
+    /// cancelled.
+    ///
+    /// [`PodbeanError::Cancelled`]: crate::PodbeanError::Cancelled
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Sets the timeout for transferring the file.
+    ///
+    /// Overrides the client's upload timeout for this upload. The timeout
+    /// of the API calls made around the transfer is not affected.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Authorizes the upload with the token of `podcast_id`.
+    ///
+    /// Needed when only podcast tokens were obtained with
+    /// [`authorize_podcasts`](crate::PodbeanClient::authorize_podcasts)
+    /// and several podcasts are managed.
+    pub fn podcast(mut self, podcast_id: &PodcastId) -> Self {
+        self.podcast_id = Some(podcast_id.clone());
+        self
+    }
+}
+
+impl fmt::Debug for UploadOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadOptions")
+            .field("progress", &self.progress.is_some())
+            .field("cancellation_token", &self.cancellation_token)
+            .field("timeout", &self.timeout)
+            .field("podcast_id", &self.podcast_id)
+            .finish()
+    }
+}
+
+/// A stream of file chunks that reports how many bytes have been sent.
+pub(crate) struct ProgressStream<S> {
+    inner: S,
+    progress: UploadProgress,
+    callback: ProgressCallback,
+}
+
+impl<S> ProgressStream<S> {
+    /// Wraps `inner`, reporting progress towards `total_bytes` to `callback`.
+    pub(crate) fn new(inner: S, total_bytes: u64, callback: ProgressCallback) -> Self {
+        Self {
+            inner,
+            progress: UploadProgress {
+                bytes_sent: 0,
+                total_bytes,
+            },
+            callback,
+        }
+    }
+}
+
+impl<S> Stream for ProgressStream<S>
+where
+    S: Stream<Item = io::Result<Bytes>> + Unpin,
+{
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = Pin::new(&mut self.inner).poll_next(cx);
+
+        if let Poll::Ready(Some(Ok(chunk))) = &poll {
+            self.progress.bytes_sent += chunk.len() as u64;
+            (self.callback)(self.progress);
+        }
+
+        poll
+    }
+}
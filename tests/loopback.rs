@@ -0,0 +1,91 @@
+//! Logins completed through the loopback redirect listener.
+
+use podbean::{LoopbackLogin, MockServer, PodbeanError, Scopes};
+
+#[tokio::test]
+async fn exchanges_the_code_from_the_browser_redirect() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+
+    let login = LoopbackLogin::start(&client, Scopes::new()).await.unwrap();
+    assert!(login.redirect_uri().starts_with("http://127.0.0.1:"));
+    let callback = server.approve(login.authorization_url());
+    let favicon = login.redirect_uri().replace("/callback", "/favicon.ico");
+
+    let browser = async {
+        let not_found = reqwest::get(&favicon).await.unwrap();
+        let page = reqwest::get(&callback).await.unwrap();
+        (
+            not_found.status(),
+            page.status(),
+            page.text().await.unwrap(),
+        )
+    };
+    let (token, (not_found, status, page)) = tokio::join!(login.finish(), browser);
+
+    assert_eq!(not_found, reqwest::StatusCode::NOT_FOUND);
+    assert!(status.is_success());
+    assert!(page.contains("Authorization complete"));
+    assert!(token.unwrap().refresh_token().is_some());
+    assert_eq!(client.list_podcasts(None, None).await.unwrap().count, 1);
+}
+
+#[tokio::test]
+async fn reports_a_denied_login() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+
+    let login = LoopbackLogin::start(&client, Scopes::new()).await.unwrap();
+    let callback = server.deny(login.authorization_url());
+
+    let (token, page) = tokio::join!(login.finish(), reqwest::get(&callback));
+
+    assert!(matches!(
+        token.unwrap_err(),
+        PodbeanError::AuthorizationDenied { .. }
+    ));
+    assert_eq!(page.unwrap().status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn drops_idle_connections() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+
+    let login = LoopbackLogin::start(&client, Scopes::new()).await.unwrap();
+    let callback = server.approve(login.authorization_url());
+    let address = login
+        .redirect_uri()
+        .trim_start_matches("http://")
+        .replace("/callback", "");
+
+    let browser = async {
+        let _idle = tokio::net::TcpStream::connect(&address).await.unwrap();
+        reqwest::get(&callback).await.unwrap().status()
+    };
+    let (token, status) = tokio::join!(login.finish(), browser);
+
+    assert!(status.is_success());
+    assert!(token.is_ok());
+}
+
+#[tokio::test]
+async fn keeps_waiting_after_a_forged_callback() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+
+    let login = LoopbackLogin::start(&client, Scopes::new()).await.unwrap();
+    let callback = server.approve(login.authorization_url());
+    let forged = format!("{}?code=stolen&state=forged", login.redirect_uri());
+
+    let browser = async {
+        let rejected = reqwest::get(&forged).await.unwrap().status();
+        let page = reqwest::get(&callback).await.unwrap().status();
+        (rejected, page)
+    };
+    let (token, (rejected, status)) = tokio::join!(login.finish(), browser);
+
+    assert_eq!(rejected, reqwest::StatusCode::BAD_REQUEST);
+    assert!(status.is_success());
+    assert!(token.is_ok());
+}
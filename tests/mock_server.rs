@@ -0,0 +1,619 @@
+//! Request and response flows against the local mock server.
+
+use futures_util::TryStreamExt;
+use podbean::{
+    ApiErrorKind, AuthToken, AuthorizationFlow, BoxFuture, CancellationToken, EpisodeId,
+    EpisodeStatus, EpisodeUpdate, FileTokenStore, MediaFormat, MockFault, MockServer, NewEpisode,
+    PodbeanClient, PodbeanError, PodbeanResult, PodcastId, RetryPolicy, Scope, Scopes, TokenStore,
+    UploadOptions, UploadProgress,
+};
+use reqwest::Method;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Starts a server and a client authorized with client credentials.
+async fn setup() -> (MockServer, PodbeanClient) {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+    client.authorize_client_credentials().await.unwrap();
+
+    (server, client)
+}
+
+/// Counts the requests the server received for `method` and `path`.
+fn count_requests(server: &MockServer, method: Method, path: &str) -> usize {
+    server
+        .requests()
+        .iter()
+        .filter(|r| r.method == method && r.path == path)
+        .count()
+}
+
+#[tokio::test]
+async fn uploads_publishes_updates_and_deletes_an_episode() {
+    let (server, client) = setup().await;
+    let podcast_id = PodcastId::from(MockServer::PODCAST_ID);
+
+    let media_key = client
+        .upload_media("pilot.mp3".to_string(), vec![7; 4096], MediaFormat::Mp3)
+        .await
+        .unwrap();
+    assert_eq!(server.uploaded_file(&media_key).unwrap().len(), 4096);
+
+    let episode = NewEpisode::new(&podcast_id, "Pilot", "The first episode")
+        .media_key(&media_key)
+        .status(EpisodeStatus::Publish);
+    let episode = client.publish_episode(&episode).await.unwrap();
+    assert_eq!(episode.status, EpisodeStatus::Publish);
+    assert!(episode.media_url.ends_with("pilot.mp3"));
+
+    let update = EpisodeUpdate::new().title("Pilot (remastered)");
+    let updated = client.update_episode(&episode.id, &update).await.unwrap();
+    assert_eq!(updated.title, "Pilot (remastered)");
+    assert_eq!(updated.content, "The first episode");
+
+    let media = client.list_media(None, None).await.unwrap();
+    assert_eq!(media.count, 1);
+    assert_eq!(media.media[0].media_key, media_key);
+
+    client.delete_episode(&episode.id).await.unwrap();
+    let error = client.get_episode(&episode.id).await.unwrap_err();
+    assert!(matches!(error, PodbeanError::ApiError(e) if e.kind == ApiErrorKind::NotFound));
+}
+
+#[tokio::test]
+async fn streams_episodes_page_by_page() {
+    let (server, client) = setup().await;
+    let podcast_id = PodcastId::from(MockServer::PODCAST_ID);
+
+    for number in 1..=5 {
+        let title = format!("Episode {}", number);
+        let episode = NewEpisode::new(&podcast_id, &title, "Show notes");
+        let _ = client.publish_episode(&episode).await.unwrap();
+    }
+
+    let episodes: Vec<_> = client
+        .episodes_stream(Some(&podcast_id), 2)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(episodes.len(), 5);
+    assert_eq!(episodes[0].title, "Episode 5");
+    assert_eq!(count_requests(&server, Method::GET, "/v1/episodes"), 3);
+}
+
+#[tokio::test]
+async fn retries_after_rate_limiting() {
+    let (server, client) = setup().await;
+
+    server.fail_next(MockFault::RateLimited {
+        retry_after: Some(0),
+    });
+    let podcasts = client.list_podcasts(None, None).await.unwrap();
+
+    assert_eq!(podcasts.count, 1);
+    assert_eq!(count_requests(&server, Method::GET, "/v1/podcasts"), 2);
+}
+
+#[tokio::test]
+async fn refreshes_a_rejected_token() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+    let code = server.authorization_code();
+    client
+        .authorize(&code, "http://localhost/callback")
+        .await
+        .unwrap();
+
+    server.revoke_access_tokens();
+    let podcasts = client.list_podcasts(None, None).await.unwrap();
+
+    assert_eq!(
+        podcasts.podcasts[0].podcast_id.as_str(),
+        MockServer::PODCAST_ID
+    );
+    let refreshes = server
+        .requests()
+        .iter()
+        .filter(|r| r.path == "/v1/oauth/token")
+        .filter(|r| {
+            r.form()
+                .contains(&("grant_type".into(), "refresh_token".into()))
+        })
+        .count();
+    assert_eq!(refreshes, 1);
+}
+
+#[tokio::test]
+async fn does_not_retry_publishing_after_a_server_error() {
+    let (server, client) = setup().await;
+    let podcast_id = PodcastId::from(MockServer::PODCAST_ID);
+
+    server.fail_next(MockFault::ServerError);
+    let error = client
+        .publish_episode(&NewEpisode::new(&podcast_id, "Pilot", "Show notes"))
+        .await
+        .unwrap_err();
+
+    assert!(error.is_retryable());
+    assert!(server.episodes().is_empty());
+    assert_eq!(count_requests(&server, Method::POST, "/v1/episodes"), 1);
+}
+
+#[tokio::test]
+async fn times_out_slow_responses() {
+    let server = MockServer::start().await.unwrap();
+    let client = server
+        .client_builder()
+        .timeout(Duration::from_millis(100))
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+    client.authorize_client_credentials().await.unwrap();
+
+    server.fail_next(MockFault::Delay(Duration::from_secs(1)));
+    let error = client.list_podcasts(None, None).await.unwrap_err();
+
+    assert!(matches!(&error, PodbeanError::NetworkError(e) if e.is_timeout()));
+    assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn rejects_unknown_credentials() {
+    let server = MockServer::start().await.unwrap();
+    let client = PodbeanClient::builder("someone_else", "wrong_secret")
+        .base_url(&server.base_url())
+        .build()
+        .unwrap();
+
+    let error = client.authorize_client_credentials().await.unwrap_err();
+
+    assert!(error.is_auth());
+    assert!(matches!(error, PodbeanError::ApiError(e) if e.kind == ApiErrorKind::InvalidGrant));
+}
+
+#[tokio::test]
+async fn uses_the_token_of_each_podcast() {
+    let server = MockServer::start().await.unwrap();
+    server.add_podcast("second_podcast", "Second Podcast");
+    let client = server.client_builder().build().unwrap();
+
+    let podcasts = client.authorize_podcasts().await.unwrap();
+    assert_eq!(podcasts.len(), 2);
+
+    for podcast_id in &podcasts {
+        let episode = NewEpisode::new(podcast_id, "Pilot", "Show notes");
+        let episode = client.publish_episode(&episode).await.unwrap();
+        assert_eq!(&episode.podcast_id, podcast_id);
+
+        let podcast = client.get_podcast(podcast_id).await.unwrap();
+        assert_eq!(&podcast.podcast_id, podcast_id);
+
+        let episodes = client
+            .list_episodes(Some(podcast_id), None, None)
+            .await
+            .unwrap();
+        assert_eq!(episodes.count, 1);
+    }
+
+    let token_requests = server
+        .requests()
+        .iter()
+        .filter(|r| r.path.starts_with("/v1/oauth/"))
+        .count();
+    assert_eq!(token_requests, 1);
+}
+
+#[tokio::test]
+async fn introspects_and_revokes_tokens() {
+    let server = MockServer::start().await.unwrap();
+    let path = std::env::temp_dir().join(format!("podbean-revoke-{}.json", std::process::id()));
+    let store = FileTokenStore::new(&path);
+    let client = server
+        .client_builder()
+        .build()
+        .unwrap()
+        .with_token_store(store.clone());
+    let code = server.authorization_code();
+    client
+        .authorize(&code, "http://localhost/callback")
+        .await
+        .unwrap();
+    let token = store.load().await.unwrap().unwrap();
+
+    let info = client.introspect_token().await.unwrap();
+    assert!(info.is_valid);
+    assert!(info.scopes.unwrap().contains(&Scope::EpisodePublish));
+    assert!(info.expires_at.is_some());
+
+    // The client authenticates with HTTP Basic, never with form fields
+    let requests = server.requests();
+    let request = requests
+        .iter()
+        .find(|r| r.path == "/v1/oauth/debugToken")
+        .unwrap();
+    assert_eq!(request.method, Method::GET);
+    assert!(
+        request
+            .query
+            .contains(&("access_token".to_string(), token.access_token().to_string()))
+    );
+    assert!(requests.iter().all(|r| {
+        !r.form()
+            .iter()
+            .chain(&r.query)
+            .any(|(name, _)| name == "client_secret")
+    }));
+
+    client.revoke_token().await.unwrap();
+
+    assert!(store.load().await.unwrap().is_none());
+    assert!(
+        !client
+            .debug_token(token.access_token())
+            .await
+            .unwrap()
+            .is_valid
+    );
+    let error = client.list_podcasts(None, None).await.unwrap_err();
+    assert!(error.is_auth());
+}
+
+#[tokio::test]
+async fn fails_fast_without_the_required_scope() {
+    let server = MockServer::start().await.unwrap();
+    server.set_token_scopes(Scopes::from([Scope::PodcastRead, Scope::EpisodeRead]));
+    let client = server.client_builder().build().unwrap();
+    client.authorize_client_credentials().await.unwrap();
+    let podcast_id = PodcastId::from(MockServer::PODCAST_ID);
+
+    let episodes = client.list_episodes(Some(&podcast_id), None, None).await;
+    assert!(episodes.is_ok());
+
+    let error = client
+        .publish_episode(&NewEpisode::new(&podcast_id, "Pilot", "Show notes"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        PodbeanError::MissingScope(Scope::EpisodePublish)
+    ));
+    assert!(error.is_auth());
+    assert_eq!(count_requests(&server, Method::POST, "/v1/episodes"), 0);
+
+    // No scope is documented for updates, so the server has the last word
+    let error = client
+        .update_episode(
+            &EpisodeId::from("mock_episode_1"),
+            &EpisodeUpdate::new().title("Pilot"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(error, PodbeanError::ApiError(e) if e.kind == ApiErrorKind::InsufficientScope)
+    );
+    assert_eq!(count_requests(&server, Method::PUT, "/v1/episodes"), 1);
+}
+
+#[tokio::test]
+async fn completes_an_authorization_flow_with_pkce() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+    let flow = AuthorizationFlow::new("http://localhost/callback").unwrap();
+
+    let authorization_url = flow.authorization_url(&client).unwrap();
+    assert!(authorization_url.contains("code_challenge_method=S256"));
+
+    let callback = server.approve(&authorization_url);
+    flow.complete(&client, &callback).await.unwrap();
+
+    let podcasts = client.list_podcasts(None, None).await.unwrap();
+    assert_eq!(podcasts.count, 1);
+
+    let exchange = server
+        .requests()
+        .into_iter()
+        .find(|r| r.path == "/v1/oauth/token")
+        .unwrap();
+    assert!(
+        exchange
+            .form()
+            .iter()
+            .any(|(key, _)| key == "code_verifier")
+    );
+}
+
+#[tokio::test]
+async fn rejects_forged_and_denied_callbacks() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+    let flow = AuthorizationFlow::new("http://localhost/callback").unwrap();
+    let other_flow = AuthorizationFlow::new("http://localhost/callback").unwrap();
+
+    let forged = server.approve(&other_flow.authorization_url(&client).unwrap());
+    let error = flow.complete(&client, &forged).await.unwrap_err();
+    assert!(matches!(error, PodbeanError::AuthError(_)));
+
+    let denied = server.deny(&flow.authorization_url(&client).unwrap());
+    let error = flow.complete(&client, &denied).await.unwrap_err();
+    assert!(matches!(
+        error,
+        PodbeanError::AuthorizationDenied { ref error, .. } if error == "access_denied"
+    ));
+
+    assert!(
+        !server
+            .requests()
+            .iter()
+            .any(|r| r.path == "/v1/oauth/token")
+    );
+}
+
+/// A token store that fails every save after the first.
+#[derive(Debug, Default)]
+struct FullDiskStore {
+    saves: AtomicUsize,
+}
+
+impl TokenStore for FullDiskStore {
+    fn load(&self) -> BoxFuture<'_, PodbeanResult<Option<AuthToken>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    fn save<'a>(&'a self, _token: &'a AuthToken) -> BoxFuture<'a, PodbeanResult<()>> {
+        let saves = self.saves.fetch_add(1, Ordering::SeqCst);
+
+        Box::pin(async move {
+            if saves == 0 {
+                Ok(())
+            } else {
+                Err(PodbeanError::OtherError("disk full".to_string()))
+            }
+        })
+    }
+}
+
+#[tokio::test]
+async fn keeps_a_refreshed_token_when_saving_it_fails() {
+    let server = MockServer::start().await.unwrap();
+    let client = server
+        .client_builder()
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+        .with_token_store(FullDiskStore::default());
+    let code = server.authorization_code();
+    client
+        .authorize(&code, "http://localhost/callback")
+        .await
+        .unwrap();
+
+    server.revoke_access_tokens();
+    let podcasts = client.list_podcasts(None, None).await.unwrap();
+    assert_eq!(podcasts.count, 1);
+
+    client.refresh_token().await.unwrap();
+    let podcasts = client.list_podcasts(None, None).await.unwrap();
+    assert_eq!(podcasts.count, 1);
+}
+
+#[tokio::test]
+async fn uses_the_only_podcast_token_for_calls_without_a_podcast() {
+    let server = MockServer::start().await.unwrap();
+    let client = server.client_builder().build().unwrap();
+    let podcast_ids = client.authorize_podcasts().await.unwrap();
+
+    let media_key = client
+        .upload_media("pilot.mp3".to_string(), vec![7; 1024], MediaFormat::Mp3)
+        .await
+        .unwrap();
+    let episode = NewEpisode::new(&podcast_ids[0], "Pilot", "Show notes").media_key(&media_key);
+    let episode = client.publish_episode(&episode).await.unwrap();
+
+    assert_eq!(
+        client.get_episode(&episode.id).await.unwrap().title,
+        "Pilot"
+    );
+    client.delete_episode(&episode.id).await.unwrap();
+
+    let info = client.introspect_token().await.unwrap();
+    assert_eq!(info.podcast_id.as_ref(), Some(&podcast_ids[0]));
+}
+
+#[tokio::test]
+async fn uploads_and_edits_episodes_of_one_of_several_podcasts() {
+    let server = MockServer::start().await.unwrap();
+    server.add_podcast("second_podcast", "Second Podcast");
+    let client = server.client_builder().build().unwrap();
+    client.authorize_podcasts().await.unwrap();
+    let podcast_id = PodcastId::from("second_podcast");
+
+    // Without an app token, calls that do not name a podcast are ambiguous
+    let error = client
+        .upload_media("pilot.mp3".to_string(), vec![0; 16], MediaFormat::Mp3)
+        .await
+        .unwrap_err();
+    assert!(error.is_auth());
+
+    let media_key = client
+        .upload_media_from_reader_with(
+            "pilot.mp3".to_string(),
+            &b"0123456789abcdef"[..],
+            16,
+            MediaFormat::Mp3,
+            UploadOptions::new().podcast(&podcast_id),
+        )
+        .await
+        .unwrap();
+    let episode = NewEpisode::new(&podcast_id, "Pilot", "Show notes").media_key(&media_key);
+    let episode = client.publish_episode(&episode).await.unwrap();
+
+    assert!(client.get_episode(&episode.id).await.is_err());
+    let fetched = client
+        .get_episode_for(&podcast_id, &episode.id)
+        .await
+        .unwrap();
+    assert_eq!(fetched.title, "Pilot");
+
+    let updated = client
+        .update_episode_for(
+            &podcast_id,
+            &episode.id,
+            &EpisodeUpdate::new().title("Pilot (remastered)"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Pilot (remastered)");
+
+    client
+        .delete_episode_for(&podcast_id, &episode.id)
+        .await
+        .unwrap();
+    let episodes = client
+        .list_episodes(Some(&podcast_id), None, None)
+        .await
+        .unwrap();
+    assert_eq!(episodes.count, 0);
+}
+
+/// Options recording every progress report of an upload.
+fn recording_progress() -> (UploadOptions, Arc<Mutex<Vec<UploadProgress>>>) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&reports);
+    let options = UploadOptions::new().on_progress(move |progress| {
+        recorded.lock().unwrap().push(progress);
+    });
+
+    (options, reports)
+}
+
+/// Checks that progress grew steadily up to the whole file.
+fn assert_complete(reports: &[UploadProgress], file_size: u64) {
+    assert!(reports.len() > 1);
+    assert!(
+        reports
+            .windows(2)
+            .all(|w| w[0].bytes_sent < w[1].bytes_sent)
+    );
+    assert!(reports.iter().all(|report| report.total_bytes == file_size));
+    assert_eq!(reports.last().unwrap().bytes_sent, file_size);
+}
+
+/// A reader that produces one chunk and then never finishes.
+struct StalledReader(Option<Vec<u8>>);
+
+impl AsyncRead for StalledReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.0.take() {
+            Some(chunk) => {
+                buf.put_slice(&chunk);
+                Poll::Ready(Ok(()))
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Whether the server received a whole upload.
+fn received_upload(server: &MockServer) -> bool {
+    server
+        .requests()
+        .iter()
+        .any(|r| r.method == Method::PUT && r.path.starts_with("/upload/"))
+}
+
+#[tokio::test]
+async fn uploads_a_file_from_a_path_reporting_progress() {
+    let (server, client) = setup().await;
+    let content: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+    let path = std::env::temp_dir().join(format!("podbean-upload-{}.mp3", std::process::id()));
+    std::fs::write(&path, &content).unwrap();
+
+    let (options, reports) = recording_progress();
+    let media_key = client
+        .upload_media_from_path_with(&path, MediaFormat::Mp3, options)
+        .await;
+    std::fs::remove_file(&path).unwrap();
+
+    let media_key = media_key.unwrap();
+    assert_complete(&reports.lock().unwrap(), content.len() as u64);
+    assert_eq!(server.uploaded_file(&media_key).unwrap(), content);
+}
+
+#[tokio::test]
+async fn uploads_a_file_from_a_reader_reporting_progress() {
+    let (server, client) = setup().await;
+    let content = vec![3; 50_000];
+
+    let (options, reports) = recording_progress();
+    let media_key = client
+        .upload_media_from_reader_with(
+            "pilot.mp3".to_string(),
+            io::Cursor::new(content.clone()),
+            content.len() as u64,
+            MediaFormat::Mp3,
+            options,
+        )
+        .await
+        .unwrap();
+
+    assert_complete(&reports.lock().unwrap(), content.len() as u64);
+    assert_eq!(server.uploaded_file(&media_key).unwrap(), content);
+}
+
+#[tokio::test]
+async fn cancels_an_upload_in_progress() {
+    let (server, client) = setup().await;
+    let cancel = CancellationToken::new();
+
+    let on_progress = cancel.clone();
+    let options = UploadOptions::new()
+        .cancellation_token(cancel.clone())
+        .on_progress(move |_| on_progress.cancel());
+    let error = client
+        .upload_media_from_reader_with(
+            "pilot.mp3".to_string(),
+            StalledReader(Some(vec![7; 1024])),
+            1_000_000,
+            MediaFormat::Mp3,
+            options,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, PodbeanError::Cancelled));
+    assert!(cancel.is_cancelled());
+    assert!(!received_upload(&server));
+}
+
+#[tokio::test]
+async fn times_out_a_stalled_upload() {
+    let (server, client) = setup().await;
+
+    let options = UploadOptions::new().timeout(Duration::from_millis(200));
+    let error = client
+        .upload_media_from_reader_with(
+            "pilot.mp3".to_string(),
+            StalledReader(Some(vec![7; 1024])),
+            1_000_000,
+            MediaFormat::Mp3,
+            options,
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(&error, PodbeanError::NetworkError(e) if e.is_timeout()));
+    assert!(!received_upload(&server));
+}